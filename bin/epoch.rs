@@ -28,6 +28,7 @@ use std::{sync::Arc, time::Duration};
 use arturo::{Epoch, EpochChange, EpochManager, EpochStream, TransferError};
 use commonware_cryptography::ed25519;
 use futures::stream;
+use prometheus_client::registry::Registry;
 use tokio::sync::{RwLock, broadcast};
 use tracing::{debug, info, warn};
 
@@ -40,8 +41,6 @@ struct EpochState {
     epoch: Epoch,
     /// Current leader URL (if any).
     leader: Option<String>,
-    /// This node's URL.
-    self_url: String,
 }
 
 /// Health-based epoch manager.
@@ -62,6 +61,10 @@ pub struct HealthBasedEpochManager {
     peer_keys: Arc<Vec<ed25519::PublicKey>>,
     /// All node URLs (including self) in sorted order.
     all_urls: Arc<Vec<String>>,
+    /// This node's URL.
+    self_url: Arc<str>,
+    /// Index of this node's URL in `all_urls`.
+    self_idx: usize,
     /// Quorum threshold.
     quorum_threshold: usize,
 }
@@ -98,20 +101,28 @@ impl HealthBasedEpochManager {
         let mut all_urls: Vec<String> = peer_urls.clone();
         all_urls.push(self_url.clone());
         all_urls.sort();
+        let self_idx = all_urls.iter().position(|u| *u == self_url).expect("self URL is listed");
 
         let (epoch_tx, _) = broadcast::channel(16);
 
         Self {
             health_tracker: HealthTracker::new(peer_urls, health_interval),
-            state: Arc::new(RwLock::new(EpochState { epoch: 0, leader: None, self_url })),
+            state: Arc::new(RwLock::new(EpochState { epoch: 0, leader: None })),
             epoch_tx,
             public_key,
             peer_keys: Arc::new(peer_keys),
             all_urls: Arc::new(all_urls),
+            self_url: self_url.into(),
+            self_idx,
             quorum_threshold,
         }
     }
 
+    /// Registers the peer health check metrics with `registry`.
+    pub fn register_metrics(&self, registry: &mut Registry) {
+        self.health_tracker.register_metrics(registry);
+    }
+
     /// Spawns the background health polling task.
     pub fn spawn_health_poller(self, interval: Duration) -> tokio::task::JoinHandle<()> {
         tokio::spawn(async move {
@@ -132,7 +143,7 @@ impl HealthBasedEpochManager {
 
         // Build list of healthy URLs including self
         let mut candidates: Vec<String> = healthy_peers;
-        candidates.push(state.self_url.clone());
+        candidates.push(self.self_url.to_string());
         candidates.sort();
 
         // Leader is the first in sorted order
@@ -147,19 +158,26 @@ impl HealthBasedEpochManager {
             state.leader = new_leader.clone();
             state.epoch += 1;
 
-            let is_self = new_leader.as_ref() == Some(&state.self_url);
+            let is_self = new_leader.as_deref() == Some(&*self.self_url);
+            let epoch = state.epoch;
 
             info!(
-                epoch = state.epoch,
+                epoch = epoch,
                 old_leader = ?old_leader,
                 new_leader = ?new_leader,
                 is_self = is_self,
                 "leader changed"
             );
 
+            // Release the lock so subscribers can look up the new sequencer
+            drop(state);
+            let Some(sequencer) = new_leader.and_then(|url| self.key_for_url(&url)) else {
+                warn!(epoch, "no key for new leader");
+                return;
+            };
+
             // Broadcast epoch change
-            let change =
-                EpochChange { epoch: state.epoch, sequencer: self.public_key.clone(), is_self };
+            let change = EpochChange { epoch, sequencer, is_self };
 
             if self.epoch_tx.send(change).is_err() {
                 debug!("no epoch change subscribers");
@@ -173,16 +191,14 @@ impl HealthBasedEpochManager {
         let idx = self.all_urls.iter().position(|u| u == url)?;
 
         // Check if it's our key
-        let state = self.state.try_read().ok()?;
-        if url == state.self_url {
+        if idx == self.self_idx {
             return Some(self.public_key.clone());
         }
 
         // Otherwise look up in peer_keys
         // Note: peer_keys is indexed by peer order, not all_urls order
         // We need to adjust the index
-        let self_idx = self.all_urls.iter().position(|u| u == &state.self_url)?;
-        let peer_idx = if idx < self_idx { idx } else { idx - 1 };
+        let peer_idx = if idx < self.self_idx { idx } else { idx - 1 };
         self.peer_keys.get(peer_idx).cloned()
     }
 }
@@ -196,17 +212,14 @@ impl EpochManager for HealthBasedEpochManager {
     }
 
     fn sequencer(&self, epoch: Epoch) -> Option<Self::PublicKey> {
-        let state = self.state.try_read().ok()?;
-        if epoch != state.epoch {
-            warn!(
-                requested = epoch,
-                current = state.epoch,
-                "sequencer requested for non-current epoch"
-            );
+        // Copy the leader out so the lock is not held across the lookup
+        let (current, leader) = self.state.try_read().map(|s| (s.epoch, s.leader.clone())).ok()?;
+        if epoch != current {
+            warn!(requested = epoch, current, "sequencer requested for non-current epoch");
             return None;
         }
 
-        state.leader.as_ref().and_then(|url| self.key_for_url(url))
+        leader.and_then(|url| self.key_for_url(&url))
     }
 
     fn is_sequencer(&self, key: &Self::PublicKey) -> bool {
@@ -214,7 +227,7 @@ impl EpochManager for HealthBasedEpochManager {
             && self
                 .state
                 .try_read()
-                .map(|s| s.leader.as_ref() == Some(&s.self_url))
+                .map(|s| s.leader.as_deref() == Some(&*self.self_url))
                 .unwrap_or(false)
     }
 
@@ -225,27 +238,20 @@ impl EpochManager for HealthBasedEpochManager {
     }
 
     fn subscribe(&self) -> EpochStream<Self::PublicKey> {
-        let mut rx = self.epoch_tx.subscribe();
-        Box::pin(stream::poll_fn(move |cx| {
-            use std::task::Poll;
-            match rx.try_recv() {
-                Ok(change) => Poll::Ready(Some(change)),
-                Err(broadcast::error::TryRecvError::Empty) => {
-                    cx.waker().wake_by_ref();
-                    Poll::Pending
+        Box::pin(stream::unfold(self.epoch_tx.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(change) => return Some((change, rx)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
                 }
-                Err(_) => Poll::Ready(None),
             }
         }))
     }
 
-    fn validators(&self, epoch: Epoch) -> Option<Vec<Self::PublicKey>> {
-        let state = self.state.try_read().ok()?;
-        if epoch != state.epoch {
-            return None;
-        }
-
-        // All peers except the leader are validators
+    fn validators(&self, _epoch: Epoch) -> Option<Vec<Self::PublicKey>> {
+        // The validator set is static, so certificates from past epochs
+        // verify against it too
         let mut validators = self.peer_keys.as_ref().clone();
         validators.push(self.public_key.clone());
         Some(validators)
@@ -302,6 +308,26 @@ mod tests {
         assert_eq!(validators.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_key_for_url_needs_no_lock() {
+        let (_, public_key) = create_test_keys();
+        let peer_key = ed25519::PrivateKey::from_seed(1).public_key();
+        let manager = HealthBasedEpochManager::new(
+            "http://localhost:8080".to_string(),
+            vec!["http://peer1:8080".to_string()],
+            public_key.clone(),
+            vec![peer_key.clone()],
+            Duration::from_secs(1),
+            1,
+        );
+
+        // Lookups succeed while the poller holds the state lock
+        let _state = manager.state.write().await;
+        assert_eq!(manager.key_for_url("http://localhost:8080"), Some(public_key));
+        assert_eq!(manager.key_for_url("http://peer1:8080"), Some(peer_key));
+        assert_eq!(manager.key_for_url("http://unknown:8080"), None);
+    }
+
     #[tokio::test]
     async fn test_transfer_not_supported() {
         let (_, public_key) = create_test_keys();
@@ -10,6 +10,11 @@ use std::{
 };
 
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use prometheus_client::{
+    encoding::EncodeLabelSet,
+    metrics::{counter::Counter, family::Family, gauge::Gauge},
+    registry::Registry,
+};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::{debug, warn};
@@ -60,6 +65,22 @@ impl PeerHealth {
     }
 }
 
+/// Labels of a peer health check.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct CheckLabels {
+    /// Peer URL.
+    peer: String,
+    /// `healthy` or `unhealthy`.
+    result: &'static str,
+}
+
+/// Labels of a peer.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct PeerLabels {
+    /// Peer URL.
+    peer: String,
+}
+
 /// Shared state for health tracking.
 #[derive(Debug, Clone)]
 pub struct HealthTracker {
@@ -69,6 +90,10 @@ pub struct HealthTracker {
     client: reqwest::Client,
     /// Health check timeout.
     timeout: Duration,
+    /// Health check results per peer.
+    checks: Family<CheckLabels, Counter>,
+    /// Whether each peer passed its last health check.
+    healthy: Family<PeerLabels, Gauge>,
 }
 
 impl HealthTracker {
@@ -86,9 +111,28 @@ impl HealthTracker {
                 .build()
                 .expect("failed to build reqwest client"),
             timeout,
+            checks: Family::default(),
+            healthy: Family::default(),
         }
     }
 
+    /// Registers the health check metrics with `registry`.
+    pub fn register_metrics(&self, registry: &mut Registry) {
+        registry.register("health_checks", "Peer health checks, by result", self.checks.clone());
+        registry.register(
+            "peer_healthy",
+            "Whether a peer passed its last health check",
+            self.healthy.clone(),
+        );
+    }
+
+    /// Records the result of a health check of the peer at `url`.
+    fn record_check(&self, url: &str, healthy: bool) {
+        let result = if healthy { "healthy" } else { "unhealthy" };
+        self.checks.get_or_create(&CheckLabels { peer: url.to_string(), result }).inc();
+        self.healthy.get_or_create(&PeerLabels { peer: url.to_string() }).set(healthy.into());
+    }
+
     /// Check health of a single peer.
     pub async fn check_peer(&self, url: &str) -> bool {
         let health_url = format!("{url}/health");
@@ -120,6 +164,7 @@ impl HealthTracker {
 
         for url in urls {
             let healthy = self.check_peer(&url).await;
+            self.record_check(&url, healthy);
             let mut peers = self.peers.write().await;
             if let Some(peer) = peers.get_mut(&url) {
                 if healthy {
@@ -232,6 +277,21 @@ mod tests {
         assert_eq!(healthy, vec!["http://a:8080"]);
     }
 
+    #[test]
+    fn test_health_tracker_metrics() {
+        let tracker = HealthTracker::new(vec!["http://a:8080".to_string()], Duration::from_secs(5));
+        tracker.record_check("http://a:8080", true);
+        tracker.record_check("http://a:8080", false);
+
+        let mut registry = Registry::default();
+        tracker.register_metrics(&mut registry);
+        let mut text = String::new();
+        prometheus_client::encoding::text::encode(&mut text, &registry).unwrap();
+        assert!(text.contains(r#"health_checks_total{peer="http://a:8080",result="healthy"} 1"#));
+        assert!(text.contains(r#"health_checks_total{peer="http://a:8080",result="unhealthy"} 1"#));
+        assert!(text.contains(r#"peer_healthy{peer="http://a:8080"} 0"#));
+    }
+
     #[tokio::test]
     async fn test_health_state() {
         let state = HealthState::new("node1".to_string());
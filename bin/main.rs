@@ -24,19 +24,21 @@ mod epoch;
 mod health;
 mod payload;
 mod rpc;
+mod sync;
 
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use arturo::{Conductor, ConductorConfig};
 use commonware_cryptography::{Signer as _, ed25519};
 use futures::StreamExt;
+use prometheus_client::registry::Registry;
 use tokio::signal;
-use tracing::info;
+use tracing::{info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::{
     config::Config, epoch::HealthBasedEpochManager, health::HealthState, payload::OpPayload,
-    rpc::create_router,
+    rpc::create_router, sync::HttpSyncSource,
 };
 
 #[tokio::main]
@@ -82,10 +84,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     // Create conductor
-    let conductor_config = ConductorConfig { quorum_threshold: config.quorum_threshold };
+    let conductor_config =
+        ConductorConfig { quorum_threshold: config.quorum_threshold, ..Default::default() };
     let conductor: Conductor<OpPayload, HealthBasedEpochManager, ed25519::PrivateKey> =
         Conductor::new(conductor_config, epoch_manager.clone(), signer);
 
+    // Catch up on payloads certified while this node was offline
+    let sync_timeout = Duration::from_millis(config.health_interval_ms.max(1000));
+    for peer in &config.peers {
+        let source = HttpSyncSource::new(peer.clone(), sync_timeout, config.peers.len() + 1);
+        match conductor.sync(&source).await {
+            Ok(applied) => info!(peer = %peer, applied, "synced from peer"),
+            Err(e) => warn!(peer = %peer, error = %e, "failed to sync from peer"),
+        }
+    }
+
     // Start the conductor
     conductor.start().await;
 
@@ -96,21 +109,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let health_interval = Duration::from_millis(config.health_interval_ms);
     let _health_handle = epoch_manager.clone().spawn_health_poller(health_interval);
 
-    // Spawn epoch change listener
-    let conductor_clone = conductor.clone();
+    // Mirror epoch changes into the health state; the conductor applies
+    // them on its own. Like the conductor, decide leadership by comparing
+    // our key with the sequencer rather than trusting `is_self`.
     let health_state_clone = health_state.clone();
-    let _epoch_handle = tokio::spawn(async move {
-        let mut stream = conductor_clone.leader_channel();
-        while let Some(change) = stream.next().await {
-            info!(epoch = change.epoch, is_self = change.is_self, "epoch change received");
-            conductor_clone.handle_epoch_change(change.clone()).await;
+    let mut changes = conductor.leader_channel();
+    let self_key = public_key.clone();
+    let _health_epoch_handle = tokio::spawn(async move {
+        while let Some(change) = changes.next().await {
             health_state_clone.set_epoch(change.epoch).await;
-            health_state_clone.set_is_leader(change.is_self).await;
+            health_state_clone.set_is_leader(change.sequencer == self_key).await;
         }
     });
 
+    // Collect metrics for GET /metrics
+    let mut registry = Registry::with_prefix("arturo");
+    conductor.metrics().register(&mut registry);
+    epoch_manager.register_metrics(&mut registry);
+
     // Create router
-    let router = create_router(conductor.clone(), health_state);
+    let router = create_router(conductor.clone(), health_state, Arc::new(registry));
 
     // Start HTTP server
     let listener = tokio::net::TcpListener::bind(config.bind_addr).await?;
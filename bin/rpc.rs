@@ -7,16 +7,28 @@
 //! - `POST /acknowledge` - Validator acknowledgment
 //! - `GET /latest` - Latest certified payload
 //! - `GET /payload/:height` - Get payload by height
+//! - `GET /certificate/:height` - Get the quorum certificate for a height
+//! - `GET /sync/:from/:to` - Certified payloads and certificates for catch-up
+//! - `GET /equivocations` - Evidence of validators signing conflicting digests
+//! - `GET /queue` - Proposal queue depth and oldest pending age
+//! - `GET /metrics` - Prometheus metrics
 
-use arturo::{Conductor, Payload};
+use std::sync::Arc;
+
+use arturo::{
+    Acknowledgment, Certificate, Conductor, ConductorError, Equivocation, Payload, QueueStatus,
+    SyncSource as _,
+};
 use axum::{
     Json, Router,
     extract::{Path, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::IntoResponse,
     routing::{get, post},
 };
-use commonware_cryptography::ed25519;
+use commonware_codec::{Decode, Encode as _};
+use commonware_cryptography::{ed25519, sha256};
+use prometheus_client::{encoding::text::encode, registry::Registry};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -35,6 +47,8 @@ pub struct AppState {
     pub conductor: OpConductor,
     /// Health state for the /health endpoint.
     pub health: HealthState,
+    /// Metrics served by the /metrics endpoint.
+    pub registry: Arc<Registry>,
 }
 
 /// Leader status response.
@@ -63,18 +77,273 @@ pub struct CommitResponse {
     /// Error message if failed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Machine-readable reason if the payload failed validation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<ValidationFailure>,
+}
+
+/// Machine-readable reason a committed payload failed validation.
+///
+/// Serialized with a `kind` tag, e.g.
+/// `{"kind":"invalid_height","expected":1,"got":5}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValidationFailure {
+    /// The payload height does not follow the newest payload.
+    InvalidHeight {
+        /// The expected height.
+        expected: u64,
+        /// The payload's height.
+        got: u64,
+    },
+    /// The payload's parent is not the newest payload.
+    ParentMismatch {
+        /// Digest of the newest payload, hex encoded, or `none`.
+        expected: String,
+        /// The payload's parent digest, hex encoded.
+        got: String,
+    },
+    /// The application-level validator rejected the payload.
+    Rejected {
+        /// The validator's reason.
+        reason: String,
+    },
+}
+
+impl ValidationFailure {
+    /// Returns the reason for `error`, or `None` if it is not a validation
+    /// failure.
+    pub fn from_error(error: &ConductorError) -> Option<Self> {
+        match error {
+            ConductorError::InvalidHeight { expected, got } => {
+                Some(Self::InvalidHeight { expected: *expected, got: *got })
+            }
+            ConductorError::ParentMismatch { expected, got } => {
+                Some(Self::ParentMismatch { expected: expected.clone(), got: got.clone() })
+            }
+            ConductorError::ValidationFailed(reason) => {
+                Some(Self::Rejected { reason: reason.clone() })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Acknowledge request.
+///
+/// Binary fields are hex encoded, with or without a `0x` prefix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcknowledgeRequest {
+    /// Public key of the acknowledging validator.
+    pub validator: String,
+    /// Epoch the acknowledgment was signed in.
+    pub epoch: u64,
+    /// Height of the pending payload being acknowledged.
+    pub height: u64,
+    /// Digest of the pending payload being acknowledged.
+    pub digest: String,
+    /// The validator's signature over the epoch, height and digest.
+    pub signature: String,
+}
+
+impl AcknowledgeRequest {
+    /// Decodes the request into a signed acknowledgment.
+    pub fn into_ack(self) -> Result<Acknowledgment<ed25519::PublicKey, sha256::Digest>, String> {
+        Ok(Acknowledgment {
+            validator: decode_hex("validator", &self.validator)?,
+            epoch: self.epoch,
+            height: self.height,
+            digest: decode_hex("digest", &self.digest)?,
+            signature: decode_hex("signature", &self.signature)?,
+        })
+    }
+}
+
+/// Decodes a hex-encoded codec value, naming `field` in any error.
+pub fn decode_hex<T: Decode<Cfg = ()>>(field: &str, value: &str) -> Result<T, String> {
+    decode_hex_cfg(field, value, &())
+}
+
+/// Decodes a hex-encoded codec value with the given codec configuration,
+/// naming `field` in any error.
+pub fn decode_hex_cfg<T: Decode>(field: &str, value: &str, cfg: &T::Cfg) -> Result<T, String> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| format!("invalid {field} hex: {e}"))?;
+    T::decode_cfg(bytes.as_slice(), cfg).map_err(|e| format!("invalid {field}: {e}"))
 }
 
 /// Acknowledge response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AcknowledgeResponse {
-    /// Whether a payload was certified as a result of this acknowledgment.
+    /// Whether any payload was certified as a result of this acknowledgment.
     pub certified: bool,
-    /// The certified payload height if certified.
+    /// The highest certified payload height if certified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<u64>,
 }
 
+/// Quorum certificate response.
+///
+/// Binary fields are hex encoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateResponse {
+    /// Epoch the payload was certified in.
+    pub epoch: u64,
+    /// Height of the certified payload.
+    pub height: u64,
+    /// Digest of the certified payload.
+    pub digest: String,
+    /// Validator signatures over the epoch, height and digest.
+    pub signatures: Vec<CertificateSignature>,
+    /// The engine's chunk certificate, if the payload was certified by the
+    /// ordered_broadcast engine.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<ChunkProofResponse>,
+}
+
+/// The ordered_broadcast engine's proof within a [`CertificateResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkProofResponse {
+    /// Public key of the sequencer that broadcast the chunk.
+    pub sequencer: String,
+    /// The chunk's height in the sequencer's chain.
+    pub height: u64,
+    /// The engine's encoded quorum certificate over the chunk.
+    pub certificate: String,
+}
+
+/// A single validator signature within a [`CertificateResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateSignature {
+    /// Public key of the signing validator.
+    pub validator: String,
+    /// The validator's signature.
+    pub signature: String,
+}
+
+impl From<Certificate<ed25519::PublicKey, sha256::Digest>> for CertificateResponse {
+    fn from(certificate: Certificate<ed25519::PublicKey, sha256::Digest>) -> Self {
+        Self {
+            epoch: certificate.epoch,
+            height: certificate.height,
+            digest: hex::encode(certificate.digest),
+            signatures: certificate
+                .signatures
+                .iter()
+                .map(|(validator, signature)| CertificateSignature {
+                    validator: hex::encode(validator),
+                    signature: hex::encode(signature),
+                })
+                .collect(),
+            chunk: certificate.chunk.map(|chunk| ChunkProofResponse {
+                sequencer: hex::encode(chunk.sequencer),
+                height: chunk.height,
+                certificate: hex::encode(chunk.certificate),
+            }),
+        }
+    }
+}
+
+/// Maximum number of heights served by a single `GET /sync` request.
+pub const MAX_SYNC_RANGE: u64 = 256;
+
+/// Catch-up response for `GET /sync/:from/:to`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncResponse {
+    /// Certified payloads in height order, starting at `from`.
+    pub entries: Vec<SyncEntryResponse>,
+}
+
+/// A certified payload within a [`SyncResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncEntryResponse {
+    /// The certified payload.
+    pub payload: OpPayload,
+    /// The hex-encoded certificate, if the payload has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate: Option<String>,
+}
+
+/// Equivocation evidence response for `GET /equivocations`.
+///
+/// Binary fields are hex encoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquivocationResponse {
+    /// Who equivocated: `"validator"` or `"sequencer"`.
+    pub kind: String,
+    /// Public key of the equivocating validator or sequencer.
+    pub offender: String,
+    /// Epoch both digests were seen in.
+    pub epoch: u64,
+    /// Height both digests were seen at.
+    pub height: u64,
+    /// The first digest received.
+    pub first: SignedDigest,
+    /// The conflicting digest.
+    pub second: SignedDigest,
+}
+
+/// A digest and, for validator evidence, the signature over it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedDigest {
+    /// The acknowledged or relayed digest.
+    pub digest: String,
+    /// The validator's signature over the epoch, height and digest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl From<Equivocation<ed25519::PublicKey, sha256::Digest>> for EquivocationResponse {
+    fn from(equivocation: Equivocation<ed25519::PublicKey, sha256::Digest>) -> Self {
+        let signed = |ack: &Acknowledgment<ed25519::PublicKey, sha256::Digest>| SignedDigest {
+            digest: hex::encode(ack.digest),
+            signature: Some(hex::encode(&ack.signature)),
+        };
+        let relayed =
+            |digest: &sha256::Digest| SignedDigest { digest: hex::encode(digest), signature: None };
+        let (kind, first, second) = match &equivocation {
+            Equivocation::Validator { first, second } => {
+                ("validator", signed(first), signed(second))
+            }
+            Equivocation::Sequencer { first, second, .. } => {
+                ("sequencer", relayed(first), relayed(second))
+            }
+        };
+        Self {
+            kind: kind.to_string(),
+            offender: hex::encode(equivocation.offender()),
+            epoch: equivocation.epoch(),
+            height: equivocation.height(),
+            first,
+            second,
+        }
+    }
+}
+
+/// Proposal queue response for `GET /queue`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueResponse {
+    /// Number of committed payloads awaiting certification.
+    pub depth: usize,
+    /// Maximum number of payloads that may await certification at once.
+    pub capacity: usize,
+    /// Milliseconds the oldest pending payload has waited, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oldest_pending_age_ms: Option<u64>,
+}
+
+impl From<QueueStatus> for QueueResponse {
+    fn from(status: QueueStatus) -> Self {
+        Self {
+            depth: status.depth,
+            capacity: status.capacity,
+            oldest_pending_age_ms: status
+                .oldest_pending_age
+                .map(|age| u64::try_from(age.as_millis()).unwrap_or(u64::MAX)),
+        }
+    }
+}
+
 /// Error response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
@@ -83,8 +352,12 @@ pub struct ErrorResponse {
 }
 
 /// Creates the axum router with all RPC endpoints.
-pub fn create_router(conductor: OpConductor, health_state: HealthState) -> Router {
-    let state = AppState { conductor, health: health_state.clone() };
+pub fn create_router(
+    conductor: OpConductor,
+    health_state: HealthState,
+    registry: Arc<Registry>,
+) -> Router {
+    let state = AppState { conductor, health: health_state.clone(), registry };
 
     Router::new()
         .route("/health", get(health_handler))
@@ -94,6 +367,11 @@ pub fn create_router(conductor: OpConductor, health_state: HealthState) -> Route
         .route("/acknowledge", post(acknowledge_handler))
         .route("/latest", get(latest_handler))
         .route("/payload/{height}", get(payload_by_height_handler))
+        .route("/certificate/{height}", get(certificate_handler))
+        .route("/sync/{from}/{to}", get(sync_handler))
+        .route("/equivocations", get(equivocations_handler))
+        .route("/queue", get(queue_handler))
+        .route("/metrics", get(metrics_handler))
         .with_state(state)
 }
 
@@ -112,22 +390,58 @@ async fn commit_handler(
     Json(request): Json<CommitRequest>,
 ) -> impl IntoResponse {
     match state.conductor.commit(request.payload).await {
-        Ok(()) => (StatusCode::OK, Json(CommitResponse { success: true, error: None })),
+        Ok(_) => {
+            (StatusCode::OK, Json(CommitResponse { success: true, error: None, reason: None }))
+        }
         Err(e) => (
-            StatusCode::BAD_REQUEST,
-            Json(CommitResponse { success: false, error: Some(e.to_string()) }),
+            commit_status(&e),
+            Json(CommitResponse {
+                success: false,
+                error: Some(e.to_string()),
+                reason: ValidationFailure::from_error(&e),
+            }),
         ),
     }
 }
 
+/// Status code for a failed commit.
+///
+/// A full proposal queue is transient, so the block builder should back off
+/// and retry rather than treat the payload as invalid.
+const fn commit_status(error: &ConductorError) -> StatusCode {
+    match error {
+        ConductorError::Backpressure { .. } => StatusCode::TOO_MANY_REQUESTS,
+        _ => StatusCode::BAD_REQUEST,
+    }
+}
+
 /// Handler for `POST /acknowledge`.
-async fn acknowledge_handler(State(state): State<AppState>) -> impl IntoResponse {
-    match state.conductor.acknowledge().await {
-        Some(payload) => (
-            StatusCode::OK,
-            Json(AcknowledgeResponse { certified: true, height: Some(payload.height()) }),
-        ),
-        None => (StatusCode::OK, Json(AcknowledgeResponse { certified: false, height: None })),
+async fn acknowledge_handler(
+    State(state): State<AppState>,
+    Json(request): Json<AcknowledgeRequest>,
+) -> impl IntoResponse {
+    let ack = match request.into_ack() {
+        Ok(ack) => ack,
+        Err(error) => {
+            return (StatusCode::BAD_REQUEST, Json(ErrorResponse { error })).into_response();
+        }
+    };
+
+    match state.conductor.acknowledge(ack).await {
+        Ok(certified) => {
+            let height = certified.last().map(Payload::height);
+            (StatusCode::OK, Json(AcknowledgeResponse { certified: height.is_some(), height }))
+                .into_response()
+        }
+        Err(
+            e @ (ConductorError::UnknownValidator { .. }
+            | ConductorError::InvalidSignature
+            | ConductorError::StaleEpoch { .. }),
+        ) => {
+            (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e.to_string() })).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: e.to_string() }))
+            .into_response(),
     }
 }
 
@@ -149,8 +463,13 @@ async fn payload_by_height_handler(
     Path(height): Path<u64>,
 ) -> impl IntoResponse {
     match state.conductor.get_by_height(height).await {
-        Some(payload) => (StatusCode::OK, Json(payload)).into_response(),
-        None => (
+        Ok(Some(payload)) => (StatusCode::OK, Json(payload)).into_response(),
+        Err(e @ ConductorError::Pruned { .. }) => {
+            (StatusCode::GONE, Json(ErrorResponse { error: e.to_string() })).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: e.to_string() }))
+            .into_response(),
+        Ok(None) => (
             StatusCode::NOT_FOUND,
             Json(ErrorResponse { error: format!("payload at height {height} not found") }),
         )
@@ -158,21 +477,165 @@ async fn payload_by_height_handler(
     }
 }
 
+/// Handler for `GET /certificate/:height`.
+async fn certificate_handler(
+    State(state): State<AppState>,
+    Path(height): Path<u64>,
+) -> impl IntoResponse {
+    match state.conductor.certificate(height).await {
+        Some(certificate) => {
+            (StatusCode::OK, Json(CertificateResponse::from(certificate))).into_response()
+        }
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse { error: format!("certificate at height {height} not found") }),
+        )
+            .into_response(),
+    }
+}
+
+/// Handler for `GET /sync/:from/:to`.
+///
+/// Serves at most [`MAX_SYNC_RANGE`] heights, stopping early at the first
+/// height this node does not have.
+async fn sync_handler(
+    State(state): State<AppState>,
+    Path((from, to)): Path<(u64, u64)>,
+) -> impl IntoResponse {
+    let to = to.min(from.saturating_add(MAX_SYNC_RANGE - 1));
+    match state.conductor.automaton().fetch(from, to).await {
+        // Nothing is served from a pruned height; tell the peer it is gone
+        // rather than that the range is empty
+        Ok(entries) if entries.is_empty() => match state.conductor.get_by_height(from).await {
+            Err(e @ ConductorError::Pruned { .. }) => {
+                (StatusCode::GONE, Json(ErrorResponse { error: e.to_string() })).into_response()
+            }
+            _ => (StatusCode::OK, Json(SyncResponse { entries: Vec::new() })).into_response(),
+        },
+        Ok(entries) => {
+            let entries = entries
+                .into_iter()
+                .map(|(payload, certificate)| SyncEntryResponse {
+                    payload,
+                    certificate: certificate.map(|c| hex::encode(c.encode())),
+                })
+                .collect();
+            (StatusCode::OK, Json(SyncResponse { entries })).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: e.to_string() }))
+            .into_response(),
+    }
+}
+
+/// Handler for `GET /equivocations`.
+async fn equivocations_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let evidence: Vec<EquivocationResponse> =
+        state.conductor.equivocations().await.into_iter().map(Into::into).collect();
+    Json(evidence)
+}
+
+/// Handler for `GET /queue`.
+async fn queue_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(QueueResponse::from(state.conductor.queue_status().await))
+}
+
+/// Handler for `GET /metrics`.
+async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let mut body = String::new();
+    if let Err(e) = encode(&mut body, &state.registry) {
+        return (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: e.to_string() }))
+            .into_response();
+    }
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/openmetrics-text; version=1.0.0; charset=utf-8")],
+        body,
+    )
+        .into_response()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_commit_response_serde() {
-        let response = CommitResponse { success: true, error: None };
+        let response = CommitResponse { success: true, error: None, reason: None };
         let json = serde_json::to_string(&response).unwrap();
         assert!(!json.contains("error")); // error should be skipped when None
+        assert!(!json.contains("reason"));
 
-        let response = CommitResponse { success: false, error: Some("test error".to_string()) };
+        let response =
+            CommitResponse { success: false, error: Some("test error".to_string()), reason: None };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("test error"));
     }
 
+    #[test]
+    fn test_commit_status() {
+        let status = commit_status(&ConductorError::Backpressure { depth: 8 });
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(commit_status(&ConductorError::NotSequencer), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_queue_response_serde() {
+        let status = QueueStatus { depth: 0, capacity: 8, oldest_pending_age: None };
+        let json = serde_json::to_string(&QueueResponse::from(status)).unwrap();
+        assert_eq!(json, r#"{"depth":0,"capacity":8}"#);
+
+        let status = QueueStatus {
+            depth: 2,
+            capacity: 8,
+            oldest_pending_age: Some(std::time::Duration::from_millis(1500)),
+        };
+        let json = serde_json::to_string(&QueueResponse::from(status)).unwrap();
+        assert_eq!(json, r#"{"depth":2,"capacity":8,"oldest_pending_age_ms":1500}"#);
+    }
+
+    #[test]
+    fn test_validation_failure_from_error() {
+        let error = ConductorError::InvalidHeight { expected: 1, got: 5 };
+        let reason = ValidationFailure::from_error(&error).unwrap();
+        assert_eq!(
+            serde_json::to_value(&reason).unwrap(),
+            serde_json::json!({ "kind": "invalid_height", "expected": 1, "got": 5 })
+        );
+
+        let error = ConductorError::ParentMismatch { expected: "aa".into(), got: "bb".into() };
+        assert_eq!(
+            ValidationFailure::from_error(&error),
+            Some(ValidationFailure::ParentMismatch { expected: "aa".into(), got: "bb".into() })
+        );
+        let error = ConductorError::ValidationFailed("too large".into());
+        assert_eq!(
+            ValidationFailure::from_error(&error),
+            Some(ValidationFailure::Rejected { reason: "too large".into() })
+        );
+        assert_eq!(ValidationFailure::from_error(&ConductorError::NotSequencer), None);
+    }
+
+    #[test]
+    fn test_acknowledge_request_into_ack() {
+        use commonware_cryptography::{Hasher as _, Signer as _};
+
+        let signer = ed25519::PrivateKey::from_seed(7);
+        let digest = sha256::Sha256::hash(b"payload");
+        let ack = Acknowledgment::sign(&signer, 3, 10, digest);
+
+        let request = AcknowledgeRequest {
+            validator: hex::encode(ack.validator.as_ref()),
+            epoch: 3,
+            height: 10,
+            digest: format!("0x{}", hex::encode(digest.as_ref())),
+            signature: hex::encode(ack.signature.as_ref()),
+        };
+        assert_eq!(request.clone().into_ack().unwrap(), ack);
+
+        let request = AcknowledgeRequest { signature: "zz".to_string(), ..request };
+        assert!(request.into_ack().unwrap_err().contains("signature"));
+    }
+
     #[test]
     fn test_leader_status_serde() {
         let status = LeaderStatus { is_leader: true, epoch: 42, next_height: 100 };
@@ -0,0 +1,98 @@
+//! HTTP catch-up client.
+//!
+//! Fetches certified payloads and their certificates from a peer's
+//! `GET /latest` and `GET /sync/:from/:to` endpoints so a node that was
+//! offline can catch up before taking part again.
+
+use std::time::Duration;
+
+use arturo::{Payload, SyncEntry, SyncError, SyncSource};
+use commonware_cryptography::ed25519;
+use reqwest::StatusCode;
+
+use crate::{
+    payload::OpPayload,
+    rpc::{SyncResponse, decode_hex_cfg},
+};
+
+/// A [`SyncSource`] backed by a peer's HTTP RPC.
+#[derive(Debug, Clone)]
+pub struct HttpSyncSource {
+    /// Base URL of the peer.
+    url: String,
+    /// HTTP client for sync requests.
+    client: reqwest::Client,
+    /// Most signatures a served certificate may carry: the validator set
+    /// size.
+    max_signatures: usize,
+}
+
+impl HttpSyncSource {
+    /// Creates a sync source for the peer at `url`.
+    ///
+    /// Certificates with more than `max_signatures` signatures are rejected
+    /// before they are decoded in full.
+    pub fn new(url: String, timeout: Duration, max_signatures: usize) -> Self {
+        Self {
+            url,
+            max_signatures,
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .expect("failed to build reqwest client"),
+        }
+    }
+
+    /// Sends a GET request to `path` on the peer.
+    async fn get(&self, path: &str) -> Result<reqwest::Response, SyncError> {
+        self.client
+            .get(format!("{}{path}", self.url))
+            .send()
+            .await
+            .map_err(|e| SyncError::Source(format!("{}: {e}", self.url)))
+    }
+}
+
+impl SyncSource<OpPayload, ed25519::PublicKey> for HttpSyncSource {
+    async fn tip(&self) -> Result<Option<u64>, SyncError> {
+        let response = self.get("/latest").await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let payload: OpPayload = response
+            .error_for_status()
+            .map_err(|e| SyncError::Source(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| SyncError::Source(e.to_string()))?;
+        Ok(Some(payload.height()))
+    }
+
+    async fn fetch(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<SyncEntry<OpPayload, ed25519::PublicKey>>, SyncError> {
+        let response: SyncResponse = self
+            .get(&format!("/sync/{from}/{to}"))
+            .await?
+            .error_for_status()
+            .map_err(|e| SyncError::Source(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| SyncError::Source(e.to_string()))?;
+
+        response
+            .entries
+            .into_iter()
+            .map(|entry| {
+                let certificate = entry
+                    .certificate
+                    .map(|hex| decode_hex_cfg("certificate", &hex, &self.max_signatures))
+                    .transpose()
+                    .map_err(SyncError::Source)?;
+                Ok((entry.payload, certificate))
+            })
+            .collect()
+    }
+}
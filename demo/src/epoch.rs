@@ -120,16 +120,13 @@ impl EpochManager for RoundRobinEpochManager {
     }
 
     fn subscribe(&self) -> EpochStream<Self::PublicKey> {
-        let mut rx = self.epoch_tx.subscribe();
-        Box::pin(stream::poll_fn(move |cx| {
-            use std::task::Poll;
-            match rx.try_recv() {
-                Ok(change) => Poll::Ready(Some(change)),
-                Err(broadcast::error::TryRecvError::Empty) => {
-                    cx.waker().wake_by_ref();
-                    Poll::Pending
+        Box::pin(stream::unfold(self.epoch_tx.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(change) => return Some((change, rx)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
                 }
-                Err(_) => Poll::Ready(None),
             }
         }))
     }
@@ -46,23 +46,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         participant.start().await;
     }
 
-    // Set initial leader (participant 1 at epoch 0)
-    if let Some(first) = participants.first() {
-        let change = arturo::EpochChange {
-            epoch: 0,
-            sequencer: all_keys[0].clone(),
-            is_self: first.epoch_manager().self_index() == 0,
-        };
-        for (i, participant) in participants.iter().enumerate() {
-            let participant_change = arturo::EpochChange {
-                epoch: 0,
-                sequencer: change.sequencer.clone(),
-                is_self: i == 0,
-            };
-            participant.handle_epoch_change(participant_change).await;
-        }
-    }
-
     // Create status channel for sidecar -> TUI communication
     let (status_tx, status_rx) = status::channel();
 
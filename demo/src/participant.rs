@@ -2,8 +2,8 @@
 
 use std::sync::Arc;
 
-use arturo::{Conductor, ConductorConfig};
-use commonware_cryptography::{Signer as _, ed25519};
+use arturo::{Acknowledgment, Conductor, ConductorConfig};
+use commonware_cryptography::{Signer as _, ed25519, sha256};
 
 use crate::{epoch::RoundRobinEpochManager, payload::DemoPayload};
 
@@ -49,8 +49,10 @@ impl Participant {
 
         let epoch_manager = RoundRobinEpochManager::new(all_keys, public_key);
 
-        let config =
-            ConductorConfig { quorum_threshold: epoch_manager.participant_count() / 2 + 1 };
+        let config = ConductorConfig {
+            quorum_threshold: epoch_manager.participant_count() / 2 + 1,
+            ..Default::default()
+        };
 
         let conductor = Conductor::new(config, epoch_manager.clone(), signer);
 
@@ -94,17 +96,34 @@ impl Participant {
 
     /// Commits a payload (if this participant is the leader).
     pub async fn commit(&self, payload: DemoPayload) -> Result<(), arturo::ConductorError> {
-        self.conductor.commit(payload).await
+        self.conductor.commit(payload).await?;
+        Ok(())
     }
 
-    /// Records an acknowledgment and returns the certified payload if quorum reached.
-    pub async fn acknowledge(&self) -> Option<DemoPayload> {
-        self.conductor.acknowledge().await
+    /// Signs an acknowledgment of `payload` with this participant's key.
+    pub async fn sign_ack(
+        &self,
+        payload: &DemoPayload,
+    ) -> Acknowledgment<ed25519::PublicKey, sha256::Digest> {
+        self.conductor.sign_ack(payload).await
     }
 
-    /// Handles an epoch change.
-    pub async fn handle_epoch_change(&self, change: arturo::EpochChange<ed25519::PublicKey>) {
-        self.conductor.handle_epoch_change(change).await;
+    /// Records a signed acknowledgment and returns the payloads certified as
+    /// a result.
+    pub async fn acknowledge(
+        &self,
+        ack: Acknowledgment<ed25519::PublicKey, sha256::Digest>,
+    ) -> Result<Vec<DemoPayload>, arturo::ConductorError> {
+        self.conductor.acknowledge(ack).await
+    }
+
+    /// Records a payload certified by another participant and returns the
+    /// payloads applied as a result.
+    pub async fn certify(
+        &self,
+        payload: DemoPayload,
+    ) -> Result<Vec<DemoPayload>, arturo::ConductorError> {
+        self.conductor.certify(payload).await
     }
 }
 
@@ -98,7 +98,7 @@ impl Sidecar {
                     DemoPayload::new(conductor_height, format!("block-{block_num}").into_bytes());
 
                 // Commit from the leader
-                if leader.commit(payload).await.is_err() {
+                if leader.commit(payload.clone()).await.is_err() {
                     self.update_status(
                         "Commit failed, retrying...",
                         current_epoch,
@@ -114,9 +114,20 @@ impl Sidecar {
                     certified_blocks,
                 );
 
-                // Trigger acknowledgments on all participants
+                // Deliver a signed acknowledgment from every participant to the leader
+                let mut certified = Vec::new();
                 for participant in &self.participants {
-                    let _ = participant.acknowledge().await;
+                    let ack = participant.sign_ack(&payload).await;
+                    if let Ok(payloads) = leader.acknowledge(ack).await {
+                        certified.extend(payloads);
+                    }
+                }
+
+                // Replicate certified payloads to the other participants
+                for participant in self.participants.iter().filter(|p| p.id() != leader.id()) {
+                    for payload in &certified {
+                        let _ = participant.certify(payload.clone()).await;
+                    }
                 }
 
                 // Block is now certified
@@ -158,26 +169,12 @@ impl Sidecar {
         None
     }
 
-    /// Advances the epoch and notifies all participants.
+    /// Advances every participant's epoch manager.
+    ///
+    /// Each conductor picks up the change from its own epoch manager.
     async fn advance_epoch(&self) {
-        // Use the first participant's epoch manager to advance
-        if let Some(first) = self.participants.first() {
-            let change = first.epoch_manager().advance_epoch().await;
-
-            // Notify all participants of the epoch change
-            for participant in &self.participants {
-                // Create a participant-specific change with correct is_self flag
-                let is_self = participant.epoch_manager().self_index()
-                    == (change.epoch as usize) % participant.epoch_manager().participant_count();
-
-                let participant_change = arturo::EpochChange {
-                    epoch: change.epoch,
-                    sequencer: change.sequencer.clone(),
-                    is_self,
-                };
-
-                participant.handle_epoch_change(participant_change).await;
-            }
+        for participant in &self.participants {
+            participant.epoch_manager().advance_epoch().await;
         }
     }
 }
@@ -90,9 +90,14 @@ async fn main() {
     conductor.start().await;
     println!("Conductor started (leader: {})", conductor.leader().await);
 
-    // Commit a payload and certify via acknowledgment
+    // Commit a payload and certify via our own signed acknowledgment
     let payload = SimplePayload { height: 0, data: b"hello arturo".to_vec() };
-    conductor.commit(payload).await.expect("commit failed");
-    let certified = conductor.acknowledge().await;
-    println!("Certified payload: {:?}", certified.map(|p| p.data));
+    let receipt = conductor.commit(payload.clone()).await.expect("commit failed");
+    let ack = conductor.sign_ack(&payload).await;
+    let certified = conductor.acknowledge(ack).await.expect("acknowledge failed");
+    println!("Certified payload: {:?}", certified.first().map(|p| &p.data));
+
+    // The receipt resolves once the payload is certified
+    let committed = receipt.await.expect("payload not certified");
+    println!("Committed at height {} in epoch {}", committed.height, committed.epoch);
 }
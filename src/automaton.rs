@@ -3,48 +3,144 @@
 //! This module provides [`PayloadAutomaton`], which implements the
 //! commonware [`Automaton`] trait for generic payload types.
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    ops::RangeInclusive,
+    sync::Arc,
+    time::Instant,
+};
 
-use commonware_consensus::{Automaton, types::Epoch as ConsensusEpoch};
-use commonware_cryptography::Digest as DigestTrait;
+use commonware_codec::{Decode as _, Encode as _};
+use commonware_consensus::{
+    Automaton, ordered_broadcast::types::Context, types::Epoch as ConsensusEpoch,
+};
+use commonware_cryptography::{Digest as DigestTrait, PublicKey};
+use commonware_utils::hex;
 use futures_channel::oneshot as fc_oneshot;
-use tokio::sync::{RwLock, oneshot};
+use tokio::sync::{Notify, RwLock, broadcast};
+use tracing::{debug, warn};
 
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
 use crate::{
-    traits::Payload,
-    types::{Height, PendingPayload},
+    receipt::{CommitReceipt, ReceiptSender},
+    store::InMemoryPayloadStore,
+    traits::{
+        Payload, PayloadStore, PayloadValidator, StoreError, SyncEntry, SyncError, SyncSource,
+    },
+    types::{
+        Acknowledgment, Certificate, CertifiedPayload, ChunkProof, Committed, ConductorError,
+        Epoch, Equivocation, Height, PendingPayload, QueueStatus, RetentionPolicy, Snapshot,
+        ValidationError,
+    },
+    validator::ValidatorChain,
 };
 
 /// Context provided to the automaton for proposal and verification.
 ///
-/// This wraps the sequencer identity and height information.
-#[derive(Debug, Clone)]
-pub struct PayloadContext<K> {
-    /// The sequencer proposing the payload.
-    pub sequencer: K,
-    /// The height being proposed.
-    pub height: Height,
-}
+/// This is the ordered_broadcast chunk context: the sequencer identity and
+/// the height of the chunk in that sequencer's chain. Chunk heights are
+/// per-sequencer and do not necessarily match payload heights.
+pub type PayloadContext<K> = Context<K>;
+
+/// Default number of proposals that may be awaiting certification at once.
+pub(crate) const DEFAULT_MAX_PENDING: usize = 8;
+
+/// Default number of certified payloads buffered for each subscriber.
+pub(crate) const DEFAULT_CERTIFIED_CAPACITY: usize = 1024;
+
+/// Number of heights below the certified tip for which signed
+/// acknowledgments are kept to detect equivocation.
+const EVIDENCE_WINDOW: u64 = 128;
+
+/// Number of equivocations retained and buffered for each subscriber.
+const EVIDENCE_CAPACITY: usize = 256;
+
+/// Number of heights up to the certified tip whose certification epoch is
+/// kept to answer retried commits.
+const RECEIPT_WINDOW: u64 = 128;
+
+/// Number of heights past the next expected height within which certified
+/// payloads received out of order are buffered.
+const BUFFER_WINDOW: u64 = 1024;
+
+/// Acknowledgments seen at one epoch and height, keyed by validator.
+type Signed<K, D> = BTreeMap<K, Acknowledgment<K, D>>;
+
+/// Receipts waiting on the proposal with a digest.
+type Receipts<D> = (D, Vec<ReceiptSender<D>>);
 
 /// Internal state of the payload automaton.
-struct PayloadState<P: Payload> {
-    /// The latest certified payload.
-    latest_certified: Option<P>,
-    /// Pending payload awaiting certification.
-    pending: Option<PendingPayload<P>>,
-    /// Certified payloads indexed by height.
-    by_height: BTreeMap<Height, P>,
-    /// Pending proposal channel.
-    pending_proposal: Option<oneshot::Sender<P::Digest>>,
+///
+/// Certified payloads live in the automaton's [`PayloadStore`].
+struct PayloadState<P: Payload, K: PublicKey> {
+    /// Payloads awaiting certification, keyed by height.
+    ///
+    /// Heights are contiguous and start right after the latest certified
+    /// payload.
+    pending: BTreeMap<Height, PendingPayload<P, K>>,
+    /// When each pending height was submitted.
+    submitted_at: BTreeMap<Height, Instant>,
+    /// Proposal request parked until the next payload is submitted.
+    waiting: Option<fc_oneshot::Sender<P::Digest>>,
+    /// Payloads relayed by sequencers, keyed by digest, with their sender.
+    relayed: BTreeMap<P::Digest, (K, P)>,
+    /// Verification requests parked until their payload is relayed, with the
+    /// sequencer of the chunk being verified.
+    verifying: BTreeMap<P::Digest, Vec<(K, fc_oneshot::Sender<bool>)>>,
+    /// Certified payloads received ahead of a missing height, keyed by
+    /// height, with the epoch they were certified in and the engine's proof
+    /// of their certification, if any.
+    buffered: BTreeMap<Height, (P, Epoch, Option<ChunkProof<K>>)>,
+    /// Receipts awaiting certification of a submitted proposal, keyed by
+    /// height, with the digest of the proposal. Retried commits add a
+    /// receipt for the same proposal.
+    receipts: BTreeMap<Height, Receipts<P::Digest>>,
+    /// Epoch each recently certified height was certified in, so retried
+    /// commits of certified payloads resolve.
+    certified_epochs: BTreeMap<Height, Epoch>,
+    /// Pending batches that certify as a unit, as the last height of the
+    /// batch keyed by its first.
+    batches: BTreeMap<Height, Height>,
+    /// Engine certificates over members of a pending batch, held until every
+    /// member is certified, keyed by height, with the epoch they were
+    /// certified in.
+    locks: BTreeMap<Height, (Epoch, ChunkProof<K>)>,
+    /// Acknowledgments seen per epoch and height, keyed by validator.
+    signed: BTreeMap<(Epoch, Height), Signed<K, P::Digest>>,
+    /// Digest of the first payload each sequencer relayed per epoch and
+    /// height.
+    proposed: BTreeMap<(Epoch, Height, K), P::Digest>,
+    /// Equivocations detected, oldest first.
+    evidence: VecDeque<Equivocation<K, P::Digest>>,
+    /// Oldest epoch proposals may still be submitted in.
+    fence: Epoch,
+    /// When each retained height was certified, under
+    /// [`RetentionPolicy::KeepFor`].
+    certified_at: BTreeMap<Height, Instant>,
+    /// Oldest height not yet pruned from the store.
+    retained_from: Height,
 }
 
-impl<P: Payload> Default for PayloadState<P> {
+impl<P: Payload, K: PublicKey> Default for PayloadState<P, K> {
     fn default() -> Self {
         Self {
-            latest_certified: None,
-            pending: None,
-            by_height: BTreeMap::new(),
-            pending_proposal: None,
+            pending: BTreeMap::new(),
+            submitted_at: BTreeMap::new(),
+            waiting: None,
+            relayed: BTreeMap::new(),
+            verifying: BTreeMap::new(),
+            buffered: BTreeMap::new(),
+            receipts: BTreeMap::new(),
+            certified_epochs: BTreeMap::new(),
+            batches: BTreeMap::new(),
+            locks: BTreeMap::new(),
+            signed: BTreeMap::new(),
+            proposed: BTreeMap::new(),
+            evidence: VecDeque::new(),
+            fence: 0,
+            certified_at: BTreeMap::new(),
+            retained_from: 0,
         }
     }
 }
@@ -58,6 +154,8 @@ impl<P: Payload> Default for PayloadState<P> {
 ///
 /// * `P` - The payload type, must implement [`Payload`]
 /// * `K` - The public key type for identifying sequencers
+/// * `St` - The store certified payloads are written to, must implement
+///   [`PayloadStore`]
 ///
 /// # Example
 ///
@@ -66,145 +164,1221 @@ impl<P: Payload> Default for PayloadState<P> {
 ///
 /// let automaton: PayloadAutomaton<MyPayload, PublicKey> = PayloadAutomaton::new();
 /// ```
-pub struct PayloadAutomaton<P: Payload, K> {
-    state: Arc<RwLock<PayloadState<P>>>,
-    _key: std::marker::PhantomData<K>,
+pub struct PayloadAutomaton<P: Payload, K: PublicKey, St = InMemoryPayloadStore<P>> {
+    state: Arc<RwLock<PayloadState<P, K>>>,
+    store: St,
+    max_pending: usize,
+    retention: RetentionPolicy,
+    certified: broadcast::Sender<CertifiedPayload<P>>,
+    equivocations: broadcast::Sender<Equivocation<K, P::Digest>>,
+    validator: Arc<dyn PayloadValidator<P>>,
+    /// Notified whenever pending payloads leave the window.
+    freed: Arc<Notify>,
+    #[cfg(feature = "metrics")]
+    metrics: Metrics,
 }
 
-impl<P: Payload, K> Clone for PayloadAutomaton<P, K> {
+impl<P: Payload, K: PublicKey, St: Clone> Clone for PayloadAutomaton<P, K, St> {
     fn clone(&self) -> Self {
-        Self { state: Arc::clone(&self.state), _key: std::marker::PhantomData }
+        Self {
+            state: Arc::clone(&self.state),
+            store: self.store.clone(),
+            max_pending: self.max_pending,
+            retention: self.retention,
+            certified: self.certified.clone(),
+            equivocations: self.equivocations.clone(),
+            validator: Arc::clone(&self.validator),
+            freed: Arc::clone(&self.freed),
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics.clone(),
+        }
     }
 }
 
-impl<P: Payload, K> std::fmt::Debug for PayloadAutomaton<P, K> {
+impl<P: Payload, K: PublicKey, St> std::fmt::Debug for PayloadAutomaton<P, K, St> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("PayloadAutomaton").field("state", &"...").finish()
+        f.debug_struct("PayloadAutomaton")
+            .field("state", &"...")
+            .field("max_pending", &self.max_pending)
+            .field("retention", &self.retention)
+            .finish()
     }
 }
 
-impl<P: Payload, K> Default for PayloadAutomaton<P, K> {
+impl<P: Payload, K: PublicKey> Default for PayloadAutomaton<P, K> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<P: Payload, K> PayloadAutomaton<P, K> {
-    /// Creates a new payload automaton.
+impl<P: Payload, K: PublicKey> PayloadAutomaton<P, K> {
+    /// Creates a new payload automaton backed by an in-memory store.
     pub fn new() -> Self {
-        Self {
-            state: Arc::new(RwLock::new(PayloadState::default())),
-            _key: std::marker::PhantomData,
-        }
+        Self::with_store(InMemoryPayloadStore::new())
     }
 
     /// Creates a new automaton initialized with a genesis payload.
     pub fn with_genesis(genesis: P) -> Self {
-        let height = genesis.height();
-        let mut by_height = BTreeMap::new();
-        by_height.insert(height, genesis.clone());
+        Self::with_store(std::iter::once(genesis).collect())
+    }
+}
 
+impl<P: Payload, K: PublicKey, St: PayloadStore<P>> PayloadAutomaton<P, K, St> {
+    /// Creates a new automaton on top of the given store.
+    ///
+    /// The latest payload already in the store is treated as the latest
+    /// certified payload, so a durable store resumes where it left off.
+    pub fn with_store(store: St) -> Self {
         Self {
-            state: Arc::new(RwLock::new(PayloadState {
-                latest_certified: Some(genesis),
-                pending: None,
-                by_height,
-                pending_proposal: None,
-            })),
-            _key: std::marker::PhantomData,
+            state: Arc::new(RwLock::new(PayloadState::default())),
+            store,
+            max_pending: DEFAULT_MAX_PENDING,
+            retention: RetentionPolicy::KeepAll,
+            certified: broadcast::channel(DEFAULT_CERTIFIED_CAPACITY).0,
+            equivocations: broadcast::channel(EVIDENCE_CAPACITY).0,
+            validator: Arc::new(ValidatorChain::new()),
+            freed: Arc::new(Notify::new()),
+            #[cfg(feature = "metrics")]
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Returns the metrics the automaton updates.
+    #[cfg(feature = "metrics")]
+    pub const fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Sets the application-level validator run on committed and verified
+    /// payloads.
+    ///
+    /// Accepts every payload by default.
+    pub fn with_validator(mut self, validator: impl PayloadValidator<P>) -> Self {
+        self.validator = Arc::new(validator);
+        self
+    }
+
+    /// Sets the maximum number of proposals awaiting certification at once.
+    ///
+    /// Defaults to 8.
+    pub const fn with_max_pending(mut self, max_pending: usize) -> Self {
+        self.max_pending = max_pending;
+        self
+    }
+
+    /// Sets how much certified history is kept in the store.
+    ///
+    /// Defaults to [`RetentionPolicy::KeepAll`].
+    pub const fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Sets how many certified payloads are buffered for each subscriber.
+    ///
+    /// Subscribers that fall further behind are told how many payloads they
+    /// missed. Defaults to 1024. Must be called before subscribing.
+    pub fn with_certified_capacity(mut self, capacity: usize) -> Self {
+        self.certified = broadcast::channel(capacity).0;
+        self
+    }
+
+    /// Subscribes to payloads as they are certified, in height order.
+    pub fn subscribe_certified(&self) -> broadcast::Receiver<CertifiedPayload<P>> {
+        self.certified.subscribe()
+    }
+
+    /// Subscribes to equivocations as they are detected.
+    pub fn subscribe_equivocations(&self) -> broadcast::Receiver<Equivocation<K, P::Digest>> {
+        self.equivocations.subscribe()
+    }
+
+    /// Returns the most recently detected equivocations, oldest first.
+    pub async fn equivocations(&self) -> Vec<Equivocation<K, P::Digest>> {
+        self.state.read().await.evidence.iter().cloned().collect()
+    }
+
+    /// Records the digest `ack` signed at its epoch and height, returning
+    /// the evidence if the validator already signed a different one there.
+    fn record_signed(
+        &self,
+        state: &mut PayloadState<P, K>,
+        ack: &Acknowledgment<K, P::Digest>,
+    ) -> Option<Equivocation<K, P::Digest>> {
+        let signed = state.signed.entry((ack.epoch, ack.height)).or_default();
+        let Some(first) = signed.get(&ack.validator) else {
+            signed.insert(ack.validator.clone(), ack.clone());
+            return None;
+        };
+        if first.digest == ack.digest {
+            return None;
+        }
+
+        let equivocation = Equivocation::Validator { first: first.clone(), second: ack.clone() };
+        warn!(
+            epoch = ack.epoch,
+            height = ack.height,
+            validator = %ack.validator,
+            "validator signed conflicting digests"
+        );
+        self.report(state, equivocation.clone());
+        Some(equivocation)
+    }
+
+    /// Records the digest of `payload`, relayed by `sequencer` in `epoch`,
+    /// reporting an [`Equivocation`] if the sequencer already relayed a
+    /// different payload at the same epoch and height.
+    fn record_proposed(
+        &self,
+        state: &mut PayloadState<P, K>,
+        epoch: Epoch,
+        sequencer: &K,
+        payload: &P,
+    ) {
+        let height = payload.height();
+        let digest = payload.digest();
+        let first = *state.proposed.entry((epoch, height, sequencer.clone())).or_insert(digest);
+        if first == digest {
+            return;
+        }
+
+        warn!(epoch, height, %sequencer, "sequencer proposed conflicting payloads");
+        let equivocation = Equivocation::Sequencer {
+            sequencer: sequencer.clone(),
+            epoch,
+            height,
+            first,
+            second: digest,
+        };
+        self.report(state, equivocation);
+    }
+
+    /// Retains `equivocation` as evidence and publishes it to subscribers.
+    fn report(&self, state: &mut PayloadState<P, K>, equivocation: Equivocation<K, P::Digest>) {
+        if state.evidence.len() == EVIDENCE_CAPACITY {
+            state.evidence.pop_front();
+        }
+        state.evidence.push_back(equivocation.clone());
+        // No subscribers is not an error
+        let _ = self.equivocations.send(equivocation);
+    }
+
+    /// Notifies subscribers that `payload` was certified in `epoch`.
+    ///
+    /// Resolves the receipt for `payload`, and fails receipts for any other
+    /// proposal at or below its height as superseded. Batches starting at or
+    /// below its height no longer certify as a unit. Wakes commits waiting
+    /// for room in the pending window.
+    fn notify_certified(&self, state: &mut PayloadState<P, K>, payload: &P, epoch: Epoch) {
+        let height = payload.height();
+        state.batches = state.batches.split_off(&(height + 1));
+        state.locks = state.locks.split_off(&(height + 1));
+        #[cfg(feature = "metrics")]
+        if let Some(submitted_at) = state.submitted_at.get(&height) {
+            self.metrics.certified(submitted_at.elapsed());
+        }
+        state.submitted_at = state.submitted_at.split_off(&(height + 1));
+        self.freed.notify_waiters();
+        #[cfg(feature = "metrics")]
+        self.metrics.pending(state.pending.len());
+        let waiting = state.receipts.split_off(&(height + 1));
+        for (receipt_height, (digest, senders)) in std::mem::replace(&mut state.receipts, waiting) {
+            let outcome = if receipt_height == height && digest == payload.digest() {
+                Ok(Committed { height, digest, epoch })
+            } else {
+                Err(ConductorError::Superseded { height: receipt_height })
+            };
+            for sender in senders {
+                // The caller may have dropped the receipt
+                let _ = sender.send(outcome.clone());
+            }
+        }
+        state.proposed.retain(|(_, proposed, _), _| proposed + EVIDENCE_WINDOW > height);
+        state.certified_epochs.insert(height, epoch);
+        let oldest = (height + 1).saturating_sub(RECEIPT_WINDOW);
+        state.certified_epochs = state.certified_epochs.split_off(&oldest);
+
+        let certified = CertifiedPayload { payload: payload.clone(), height, epoch };
+        // No subscribers is not an error
+        let _ = self.certified.send(certified);
+    }
+
+    /// Fails every outstanding receipt with the error `reason` builds for
+    /// its height.
+    fn fail_receipts(state: &mut PayloadState<P, K>, reason: impl Fn(Height) -> ConductorError) {
+        for (height, (_, senders)) in std::mem::take(&mut state.receipts) {
+            for sender in senders {
+                let _ = sender.send(Err(reason(height)));
+            }
+        }
+    }
+
+    /// Removes the proposal pending at the height of the certified
+    /// `payload`.
+    ///
+    /// A different proposal pending there lost to `payload`, and so did
+    /// every proposal above it, since they extend it: all of them are
+    /// dropped and their receipts fail with [`ConductorError::Superseded`].
+    fn supersede_pending(state: &mut PayloadState<P, K>, payload: &P) {
+        let height = payload.height();
+        let Some(pending) = state.pending.remove(&height) else {
+            return;
+        };
+        if pending.payload.digest() == payload.digest() {
+            return;
+        }
+
+        let orphaned = state.pending.split_off(&height);
+        state.batches.retain(|start, _| *start < height);
+        state.locks.retain(|locked, _| *locked < height);
+        state.submitted_at.retain(|submitted, _| *submitted < height);
+        for (receipt_height, (_, senders)) in state.receipts.split_off(&(height + 1)) {
+            for sender in senders {
+                let _ = sender.send(Err(ConductorError::Superseded { height: receipt_height }));
+            }
         }
+        warn!(height, dropped = orphaned.len() + 1, "certified payload superseded local proposals");
+    }
+
+    /// Returns a reference to the underlying store.
+    pub const fn store(&self) -> &St {
+        &self.store
     }
 
     /// Returns the latest certified payload.
     pub async fn latest(&self) -> Option<P> {
-        self.state.read().await.latest_certified.clone()
+        self.store.latest().await
     }
 
     /// Returns the expected next height.
+    ///
+    /// Accounts for payloads that are in flight but not yet certified.
     pub async fn next_height(&self) -> Height {
-        self.state.read().await.latest_certified.as_ref().map(|p| p.height() + 1).unwrap_or(0)
+        let state = self.state.read().await;
+        self.tip(&state).await.map(|(height, _)| height + 1).unwrap_or(0)
+    }
+
+    /// Returns the certified payload at `height`, or `None` if no payload
+    /// was certified at that height.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConductorError::Pruned`] if the payload was pruned from the
+    /// store by the [`RetentionPolicy`].
+    pub async fn get_by_height(&self, height: Height) -> Result<Option<P>, ConductorError> {
+        if let Some(payload) = self.store.get_by_height(height).await {
+            return Ok(Some(payload));
+        }
+        let retained_from = self.retained_from().await;
+        if height < retained_from {
+            return Err(ConductorError::Pruned { height, retained_from });
+        }
+        Ok(None)
+    }
+
+    /// Returns the oldest height not pruned by the [`RetentionPolicy`].
+    ///
+    /// A durable store reports where its retained history starts, so the
+    /// retention point survives restarts.
+    pub async fn retained_from(&self) -> Height {
+        let state = self.state.read().await;
+        self.retention_point(&state).await
+    }
+
+    /// Returns the oldest height not pruned, seeded from the store.
+    async fn retention_point(&self, state: &PayloadState<P, K>) -> Height {
+        state.retained_from.max(self.store.retained_from().await)
+    }
+
+    /// Captures the retained certified history and the latest certificate.
+    ///
+    /// The snapshot's epoch is the automaton's current [fence](Self::fence).
+    /// Proposals still awaiting certification are not included.
+    pub async fn snapshot(&self) -> Snapshot<P, K> {
+        let state = self.state.read().await;
+        let Some(latest) = self.store.latest().await else {
+            return Snapshot { epoch: state.fence, history: Vec::new(), certificate: None };
+        };
+
+        let mut history = Vec::new();
+        for height in self.retention_point(&state).await..latest.height() {
+            if let Some(payload) = self.store.get_by_height(height).await {
+                history.push(payload);
+            }
+        }
+        let certificate = self.certificate(latest.height()).await;
+        history.push(latest);
+        Snapshot { epoch: state.fence, history, certificate }
+    }
+
+    /// Bootstraps the automaton from a trusted `snapshot`.
+    ///
+    /// The snapshot's history is written to the store and its latest payload
+    /// becomes the certified tip. Proposals awaiting certification and relayed
+    /// payloads that no longer extend the chain are discarded, and proposals
+    /// from epochs before the snapshot's are fenced off. The certificate is
+    /// stored as is: the snapshot is trusted, so signatures are not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConductorError::InvalidSnapshot`] if the snapshot is empty,
+    /// its history is not a chain, its certificate is not for its latest
+    /// payload, or it is not ahead of the local chain. Returns
+    /// [`ConductorError::Store`] if the history could not be stored.
+    pub async fn restore(&self, snapshot: Snapshot<P, K>) -> Result<(), ConductorError> {
+        let invalid = |reason: &str| Err(ConductorError::InvalidSnapshot(reason.to_string()));
+        let Snapshot { epoch, history, certificate } = snapshot;
+        let (Some(first), Some(latest)) = (history.first(), history.last()) else {
+            return invalid("empty history");
+        };
+        let linked = history
+            .windows(2)
+            .all(|pair| Self::extends(&pair[1], Some((pair[0].height(), pair[0].digest()))));
+        if !linked {
+            return invalid("history is not a chain");
+        }
+        if certificate.as_ref().is_some_and(|certificate| {
+            certificate.height != latest.height() || certificate.digest != latest.digest()
+        }) {
+            return invalid("certificate does not match the latest payload");
+        }
+
+        let mut state = self.state.write().await;
+        if self.store.latest().await.is_some_and(|local| local.height() >= latest.height()) {
+            return invalid("snapshot is not ahead of the local chain");
+        }
+
+        for payload in &history {
+            self.store.store(payload).await?;
+        }
+        if let Some(certificate) = certificate {
+            self.store.store_certificate(certificate.height, &certificate.encode()).await?;
+        }
+
+        let tip = latest.height();
+        state.pending.clear();
+        state.submitted_at.clear();
+        state.batches.clear();
+        state.locks.clear();
+        Self::fail_receipts(&mut state, |height| ConductorError::Superseded { height });
+        self.freed.notify_waiters();
+        #[cfg(feature = "metrics")]
+        self.metrics.pending(0);
+        state.waiting = None;
+        state.relayed.retain(|_, (_, payload)| payload.height() > tip);
+        state.fence = state.fence.max(epoch);
+        state.certified_at.clear();
+        state.certified_epochs.clear();
+        state.retained_from = first.height();
+        debug!(from = first.height(), tip, epoch, "restored snapshot");
+        Ok(())
+    }
+
+    /// Applies a payload fetched while catching up, certified in `epoch`.
+    ///
+    /// The caller is responsible for verifying `certificate`. The payload
+    /// must extend the latest certified payload. Proposals and relayed
+    /// payloads at or below its height are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SyncError::InvalidPayload`] if the payload does not extend
+    /// the latest certified payload, or [`ConductorError::Store`] if it could
+    /// not be stored.
+    pub async fn apply_synced(
+        &self,
+        payload: P,
+        certificate: Option<Certificate<K, P::Digest>>,
+        epoch: Epoch,
+    ) -> Result<(), ConductorError> {
+        let mut state = self.state.write().await;
+        let height = payload.height();
+        let tip = self.store.latest().await.map(|p| (p.height(), p.digest()));
+        if !Self::extends(&payload, tip) {
+            return Err(SyncError::InvalidPayload { height }.into());
+        }
+
+        self.store.store(&payload).await?;
+        if let Some(certificate) = certificate {
+            self.store.store_certificate(height, &certificate.encode()).await?;
+        }
+        Self::supersede_pending(&mut state, &payload);
+        state.relayed.retain(|_, (_, relayed)| relayed.height() > height);
+        self.notify_certified(&mut state, &payload, epoch);
+        let mut heights = vec![height];
+        heights.extend(self.drain_buffered(&mut state).await?.iter().map(Payload::height));
+        self.retain(&mut state, &heights).await;
+        Ok(())
+    }
+
+    /// Records newly certified `heights`, in order, and prunes the history
+    /// the [`RetentionPolicy`] no longer keeps.
+    ///
+    /// Pruning failures are logged; the payloads stay in the store and are
+    /// pruned on a later certification. The retention point only advances
+    /// when the store actually removed payloads, so a store that cannot
+    /// prune keeps serving its full history.
+    async fn retain(&self, state: &mut PayloadState<P, K>, heights: &[Height]) {
+        let Some(&latest) = heights.last() else {
+            return;
+        };
+        let below = match self.retention {
+            RetentionPolicy::KeepAll => return,
+            RetentionPolicy::KeepLast(count) => (latest + 1).saturating_sub(count.max(1)),
+            RetentionPolicy::KeepFor(window) => {
+                let now = Instant::now();
+                state.certified_at.extend(heights.iter().map(|height| (*height, now)));
+                let expired = now.checked_sub(window);
+                state
+                    .certified_at
+                    .iter()
+                    .find(|(_, at)| expired.is_none_or(|expired| **at >= expired))
+                    .map_or(latest, |(height, _)| *height)
+            }
+        };
+        let below = below.min(latest);
+        if below <= self.retention_point(state).await {
+            return;
+        }
+
+        match self.store.prune(below).await {
+            Ok(pruned) => {
+                state.certified_at = state.certified_at.split_off(&below);
+                if !pruned.is_empty() {
+                    debug!(below, pruned = pruned.len(), "pruned certified history");
+                    state.retained_from = below;
+                }
+            }
+            Err(e) => warn!(below, error = %e, "failed to prune certified history"),
+        }
+    }
+
+    /// Returns the certificate for the payload at `height`.
+    ///
+    /// Returns `None` if the payload is not certified or was certified
+    /// without a proof, e.g. via [`PayloadAutomaton::certify`].
+    pub async fn certificate(&self, height: Height) -> Option<Certificate<K, P::Digest>> {
+        let bytes = self.store.get_certificate(height).await?;
+        // Certificates are verified before they are stored, so the local
+        // copy is trusted not to carry an oversized signature set
+        match Certificate::decode_cfg(bytes.as_slice(), &usize::MAX) {
+            Ok(certificate) => Some(certificate),
+            Err(e) => {
+                warn!(height, error = %e, "failed to decode stored certificate");
+                None
+            }
+        }
+    }
+
+    /// Returns the number of payloads awaiting certification.
+    pub async fn pending_count(&self) -> usize {
+        self.state.read().await.pending.len()
+    }
+
+    /// Returns the depth and capacity of the pending window, and how long
+    /// its oldest payload has been awaiting certification.
+    pub async fn queue_status(&self) -> QueueStatus {
+        let state = self.state.read().await;
+        let oldest_pending_age = state
+            .pending
+            .first_key_value()
+            .and_then(|(height, _)| state.submitted_at.get(height))
+            .map(Instant::elapsed);
+        QueueStatus { depth: state.pending.len(), capacity: self.max_pending, oldest_pending_age }
+    }
+
+    /// Waits until `count` more payloads fit in the pending window.
+    ///
+    /// Never returns if `count` exceeds the window.
+    pub async fn wait_for_room(&self, count: usize) {
+        loop {
+            // Register before checking so a payload certified in between
+            // is not missed
+            let mut freed = std::pin::pin!(self.freed.notified());
+            freed.as_mut().enable();
+            if self.state.read().await.pending.len() + count <= self.max_pending {
+                return;
+            }
+            freed.await;
+        }
+    }
+
+    /// Returns the height and digest of the newest payload, in flight or
+    /// certified.
+    async fn tip(&self, state: &PayloadState<P, K>) -> Option<(Height, P::Digest)> {
+        if let Some((height, pending)) = state.pending.last_key_value() {
+            return Some((*height, pending.payload.digest()));
+        }
+        self.store.latest().await.map(|p| (p.height(), p.digest()))
+    }
+
+    /// Returns the newest payload, in flight or certified.
+    async fn tip_payload(&self, state: &PayloadState<P, K>) -> Option<P> {
+        if let Some((_, pending)) = state.pending.last_key_value() {
+            return Some(pending.payload.clone());
+        }
+        self.store.latest().await
+    }
+
+    /// Returns a receipt for `payload` if it was already submitted, so a
+    /// retried commit succeeds instead of failing.
+    ///
+    /// If the same payload is pending, the receipt resolves once it is
+    /// certified. If it is already certified, the receipt is resolved with
+    /// the epoch it was certified in. Returns `None` if no payload is pending
+    /// or certified at its height.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConductorError::InvalidHeight`] if a different payload is
+    /// pending or certified at its height, or if it was certified too long
+    /// ago to know its epoch.
+    pub async fn existing_receipt(
+        &self,
+        payload: &P,
+    ) -> Result<Option<CommitReceipt<P::Digest>>, ConductorError> {
+        let mut state = self.state.write().await;
+        let height = payload.height();
+        let digest = payload.digest();
+        let expected = self.tip(&state).await.map_or(0, |(height, _)| height + 1);
+        let conflict = ConductorError::InvalidHeight { expected, got: height };
+
+        if let Some(pending) = state.pending.get(&height) {
+            if pending.payload.digest() != digest {
+                return Err(conflict);
+            }
+            let (receipt, sender) = CommitReceipt::new(height, digest);
+            state.receipts.entry(height).or_insert_with(|| (digest, Vec::new())).1.push(sender);
+            return Ok(Some(receipt));
+        }
+
+        let Some(certified) = self.store.get_by_height(height).await else {
+            return Ok(None);
+        };
+        if certified.digest() != digest {
+            return Err(conflict);
+        }
+        let epoch = match state.certified_epochs.get(&height) {
+            Some(epoch) => Some(*epoch),
+            None => self.certificate(height).await.map(|certificate| certificate.epoch),
+        };
+        let Some(epoch) = epoch else {
+            return Err(conflict);
+        };
+        let (receipt, sender) = CommitReceipt::new(height, digest);
+        let _ = sender.send(Ok(Committed { height, digest, epoch }));
+        Ok(Some(receipt))
+    }
+
+    /// Submits a payload for proposal in `epoch`.
+    ///
+    /// This is called by the conductor when acting as sequencer. The payload
+    /// must extend the newest in-flight payload. Returns a [`CommitReceipt`]
+    /// that resolves once the payload is certified, or fails if it is
+    /// superseded or dropped by [`fence`](Self::fence).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConductorError::StaleEpoch`] if `epoch` is older than the
+    /// current [fence](Self::fence), [`ConductorError::WindowFull`] if the
+    /// maximum number of proposals are already awaiting certification, or
+    /// [`ConductorError::InvalidHeight`] if the payload does not extend the
+    /// newest in-flight payload.
+    pub async fn submit_proposal(
+        &self,
+        payload: P,
+        epoch: Epoch,
+        threshold: usize,
+    ) -> Result<CommitReceipt<P::Digest>, ConductorError> {
+        let mut state = self.state.write().await;
+
+        if epoch < state.fence {
+            return Err(ConductorError::StaleEpoch { epoch, current: state.fence });
+        }
+
+        if state.pending.len() >= self.max_pending {
+            return Err(ConductorError::WindowFull { limit: self.max_pending });
+        }
+
+        let expected = self.tip(&state).await.map(|(height, _)| height + 1).unwrap_or(0);
+        if payload.height() != expected {
+            return Err(ConductorError::InvalidHeight { expected, got: payload.height() });
+        }
+
+        let height = payload.height();
+        let digest = payload.digest();
+        let (receipt, sender) = CommitReceipt::new(height, digest);
+        state.pending.insert(height, PendingPayload::new(payload, epoch, threshold));
+        state.submitted_at.insert(height, Instant::now());
+        state.receipts.insert(height, (digest, vec![sender]));
+        #[cfg(feature = "metrics")]
+        self.metrics.pending(state.pending.len());
+
+        // Hand the payload to a proposal request parked on an empty window
+        if let Some(waiting) = state.waiting.take() {
+            let _ = waiting.send(digest);
+        }
+
+        Ok(receipt)
+    }
+
+    /// Submits a batch of consecutive payloads for proposal in `epoch`.
+    ///
+    /// The batch must form a parent-linked chain extending the newest
+    /// in-flight payload. It is submitted as a whole or not at all, and
+    /// [`acknowledge`](Self::acknowledge) certifies it as a unit once every
+    /// payload in it reached quorum. Returns a [`CommitReceipt`] per payload,
+    /// in height order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConductorError::ValidationFailed`] if the batch is empty,
+    /// [`ConductorError::StaleEpoch`] if `epoch` is older than the current
+    /// [fence](Self::fence), [`ConductorError::WindowFull`] if the batch does
+    /// not fit in the pending window, or [`ConductorError::InvalidHeight`] or
+    /// [`ConductorError::ParentMismatch`] if a payload does not extend its
+    /// predecessor, or [`ConductorError::ValidationFailed`] if the
+    /// [validator](Self::with_validator) rejects a payload.
+    pub async fn submit_batch(
+        &self,
+        payloads: Vec<P>,
+        epoch: Epoch,
+        threshold: usize,
+    ) -> Result<Vec<CommitReceipt<P::Digest>>, ConductorError> {
+        let mut state = self.state.write().await;
+        let (Some(first), Some(last)) = (payloads.first(), payloads.last()) else {
+            return Err(ConductorError::ValidationFailed("empty batch".to_string()));
+        };
+        let (start, end) = (first.height(), last.height());
+
+        if epoch < state.fence {
+            return Err(ConductorError::StaleEpoch { epoch, current: state.fence });
+        }
+
+        if state.pending.len() + payloads.len() > self.max_pending {
+            return Err(ConductorError::WindowFull { limit: self.max_pending });
+        }
+
+        let tip = self.tip_payload(&state).await;
+        let mut parent = tip.as_ref();
+        for payload in &payloads {
+            self.validate_against(payload, parent)?;
+            parent = Some(payload);
+        }
+
+        let mut receipts = Vec::with_capacity(payloads.len());
+        let submitted_at = Instant::now();
+        for payload in payloads {
+            let height = payload.height();
+            let digest = payload.digest();
+            let (receipt, sender) = CommitReceipt::new(height, digest);
+            state.pending.insert(height, PendingPayload::new(payload, epoch, threshold));
+            state.submitted_at.insert(height, submitted_at);
+            state.receipts.insert(height, (digest, vec![sender]));
+            receipts.push(receipt);
+        }
+        if end > start {
+            state.batches.insert(start, end);
+        }
+        #[cfg(feature = "metrics")]
+        self.metrics.pending(state.pending.len());
+
+        // Hand the first payload to a proposal request parked on an empty
+        // window
+        if let Some(waiting) = state.waiting.take() {
+            let _ = waiting.send(*receipts[0].digest());
+        }
+
+        debug!(start, end, "submitted batch");
+        Ok(receipts)
+    }
+
+    /// Cancels a proposal request parked waiting for the next payload.
+    ///
+    /// Called on epoch change so a request made under the previous epoch
+    /// does not pick up a payload submitted under the next one. The engine
+    /// sees the dropped receiver and proposes again for the new epoch.
+    /// Returns whether a parked request was cancelled.
+    pub async fn cancel_proposal(&self) -> bool {
+        let cancelled = self.state.write().await.waiting.take().is_some();
+        if cancelled {
+            debug!("cancelled parked proposal request");
+        }
+        cancelled
+    }
+
+    /// Fences proposals from epochs older than `epoch`.
+    ///
+    /// Called on epoch change so a sequencer that lost leadership cannot
+    /// certify payloads proposed under its old epoch. Proposals still in
+    /// flight are carried into `epoch` with their acknowledgments cleared if
+    /// `retain` is set, i.e. the node is still the sequencer; otherwise they
+    /// are dropped and returned in height order. Any parked proposal request
+    /// is cancelled. Fencing an epoch at or below the current fence is a
+    /// no-op.
+    pub async fn fence(&self, epoch: Epoch, retain: bool) -> Vec<P> {
+        let mut state = self.state.write().await;
+        if epoch <= state.fence {
+            return Vec::new();
+        }
+        state.fence = epoch;
+        if state.waiting.take().is_some() {
+            debug!(epoch, "cancelled parked proposal request");
+        }
+
+        if retain {
+            for pending in state.pending.values_mut() {
+                pending.epoch = epoch;
+                pending.signatures.clear();
+            }
+            return Vec::new();
+        }
+
+        let dropped: Vec<_> =
+            std::mem::take(&mut state.pending).into_values().map(|p| p.payload).collect();
+        state.submitted_at.clear();
+        state.batches.clear();
+        state.locks.clear();
+        Self::fail_receipts(&mut state, |height| ConductorError::EpochChanged { height, epoch });
+        self.freed.notify_waiters();
+        #[cfg(feature = "metrics")]
+        self.metrics.pending(0);
+        if !dropped.is_empty() {
+            warn!(epoch, dropped = dropped.len(), "dropped proposals from a stale epoch");
+        }
+        dropped
+    }
+
+    /// Returns the oldest epoch proposals may be submitted in.
+    pub async fn fenced_epoch(&self) -> Epoch {
+        self.state.read().await.fence
+    }
+
+    /// Records a validator's acknowledgment of a pending payload.
+    ///
+    /// The caller is responsible for checking the acknowledgment's signature
+    /// and that the validator belongs to the epoch's validator set.
+    /// Acknowledgments for heights that are not pending, for a different
+    /// digest or epoch than the proposal, or from a validator that already
+    /// acknowledged are ignored. A validator that signs a different digest
+    /// than it already signed at the same epoch and height is reported as an
+    /// [`Equivocation`], see [`PayloadAutomaton::subscribe_equivocations`].
+    ///
+    /// Payloads are certified strictly in height order: a payload that
+    /// reaches quorum ahead of its predecessors is held until they are
+    /// certified, and a batch submitted with
+    /// [`submit_batch`](Self::submit_batch) is held until all of it reached
+    /// quorum. Returns every payload certified as a result of this
+    /// acknowledgment, in height order. Each payload and its [`Certificate`]
+    /// are written to the store before the payload is released from the
+    /// pending window.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a certified payload could not be stored. That
+    /// payload and its successors stay pending in that case.
+    pub async fn acknowledge(
+        &self,
+        ack: Acknowledgment<K, P::Digest>,
+    ) -> Result<Vec<P>, StoreError> {
+        #[cfg(feature = "metrics")]
+        self.metrics.ack_received();
+        let mut state = self.state.write().await;
+
+        // Bound the evidence kept for junk far-future acknowledgments
+        let next = self.store.latest().await.map_or(0, |latest| latest.height() + 1);
+        if ack.height >= next + BUFFER_WINDOW {
+            debug!(height = ack.height, "ignoring acknowledgment far above the latest certified");
+            return Ok(Vec::new());
+        }
+        if self.record_signed(&mut state, &ack).is_some() {
+            return Ok(Vec::new());
+        }
+        let Some(pending) = state.pending.get_mut(&ack.height) else {
+            debug!(height = ack.height, "ignoring acknowledgment for height not pending");
+            return Ok(Vec::new());
+        };
+        if pending.payload.digest() != ack.digest {
+            warn!(
+                height = ack.height,
+                validator = %ack.validator,
+                "ignoring acknowledgment for a different digest"
+            );
+            return Ok(Vec::new());
+        }
+        if pending.epoch != ack.epoch {
+            debug!(
+                height = ack.height,
+                proposed = pending.epoch,
+                signed = ack.epoch,
+                "ignoring acknowledgment from another epoch"
+            );
+            return Ok(Vec::new());
+        }
+        if !pending.acknowledge(ack.validator, ack.signature) {
+            debug!(height = ack.height, "ignoring duplicate acknowledgment");
+            return Ok(Vec::new());
+        }
+
+        let mut certified = Vec::new();
+        while let Some(heights) = Self::certifiable(&state) {
+            let (start, end) = (*heights.start(), *heights.end());
+            for height in heights {
+                if let Err(error) = self.store_certified(&state.pending[&height]).await {
+                    // The rest of the batch stays pending as a unit
+                    state.batches.remove(&start);
+                    if height < end {
+                        state.batches.insert(height, end);
+                    }
+                    return Err(error);
+                }
+                if let Some(pending) = state.pending.remove(&height) {
+                    self.notify_certified(&mut state, &pending.payload, pending.epoch);
+                    certified.push(pending.payload);
+                }
+            }
+        }
+        let heights: Vec<_> = certified.iter().map(Payload::height).collect();
+        if let Some(&tip) = heights.last() {
+            state.signed.retain(|(_, height), _| height + EVIDENCE_WINDOW > tip);
+        }
+        self.retain(&mut state, &heights).await;
+
+        Ok(certified)
+    }
+
+    /// Returns the heights at the front of the pending window that can be
+    /// certified together: the oldest pending payload, or the whole batch
+    /// it starts, once every payload in it reached quorum.
+    fn certifiable(state: &PayloadState<P, K>) -> Option<RangeInclusive<Height>> {
+        let (&start, _) = state.pending.first_key_value()?;
+        let end = state.batches.get(&start).copied().unwrap_or(start);
+        (start..=end)
+            .all(|height| state.pending.get(&height).is_some_and(PendingPayload::is_certified))
+            .then_some(start..=end)
+    }
+
+    /// Writes a pending payload that reached quorum and its [`Certificate`]
+    /// to the store.
+    async fn store_certified(&self, pending: &PendingPayload<P, K>) -> Result<(), StoreError> {
+        self.store.store(&pending.payload).await?;
+        let certificate = pending.certificate();
+        self.store.store_certificate(certificate.height, &certificate.encode()).await
+    }
+
+    /// Writes a payload certified by the ordered_broadcast engine to the
+    /// store, with a [`Certificate`] carrying the engine's `proof`, if any.
+    async fn store_proven(
+        &self,
+        payload: &P,
+        epoch: Epoch,
+        proof: Option<ChunkProof<K>>,
+    ) -> Result<(), StoreError> {
+        self.store.store(payload).await?;
+        let Some(proof) = proof else {
+            return Ok(());
+        };
+        let certificate = Certificate::from_chunk(epoch, payload.height(), payload.digest(), proof);
+        self.store.store_certificate(certificate.height, &certificate.encode()).await
+    }
+
+    /// Certifies the pending or relayed payload with `digest`.
+    ///
+    /// Called when the ordered_broadcast engine reports a quorum certificate
+    /// over a chunk carrying `digest`. The sequencer certifies its own
+    /// pending payload; validators certify the payload relayed to them.
+    /// `epoch` is the epoch of the chunk's certificate, and `proof` the
+    /// certificate itself, stored as the payload's [`Certificate`]. As with
+    /// [`certify`](Self::certify), payloads are applied strictly in height
+    /// order: one ahead of the next height is buffered until the gap is
+    /// filled, and one at the next height must extend the latest certified
+    /// payload. A member of a pending batch is held until the engine
+    /// certified every member, and the batch is then applied as a unit.
+    /// Returns the certified payload, or `None` if no such payload is known,
+    /// it was held or buffered, it is already certified, or it does not
+    /// extend the latest certified payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload could not be stored. It stays pending
+    /// in that case, along with the rest of its batch.
+    pub async fn certify_digest(
+        &self,
+        digest: &P::Digest,
+        epoch: Epoch,
+        proof: ChunkProof<K>,
+    ) -> Result<Option<P>, StoreError> {
+        let mut state = self.state.write().await;
+        let Some(payload) = state
+            .pending
+            .values()
+            .map(|pending| &pending.payload)
+            .chain(state.relayed.get(digest).map(|(_, payload)| payload))
+            .find(|payload| payload.digest() == *digest)
+            .cloned()
+        else {
+            return Ok(None);
+        };
+
+        let height = payload.height();
+        let tip = self.store.latest().await.map(|p| (p.height(), p.digest()));
+        let next = tip.map_or(0, |(height, _)| height + 1);
+        if height < next {
+            debug!(height, "certified payload is already certified");
+            return Ok(None);
+        }
+
+        let is_pending =
+            state.pending.get(&height).is_some_and(|pending| pending.payload.digest() == *digest);
+        let mut members = Vec::new();
+        match Self::batch_of(&state, height).filter(|_| is_pending) {
+            Some(batch) => {
+                state.locks.insert(height, (epoch, proof));
+                if !batch.clone().all(|member| state.locks.contains_key(&member)) {
+                    debug!(height, "holding certified batch member until the batch is certified");
+                    return Ok(None);
+                }
+                for member in batch {
+                    let (epoch, proof) = state.locks.remove(&member).expect("member is locked");
+                    members.push((state.pending[&member].payload.clone(), epoch, proof));
+                }
+            }
+            None => members.push((payload.clone(), epoch, proof)),
+        }
+
+        let start = members[0].0.height();
+        let end = members.last().map_or(height, |(payload, _, _)| payload.height());
+        if start > next {
+            if end - next >= BUFFER_WINDOW {
+                warn!(height, next, "certified payload too far ahead to buffer");
+                return Ok(None);
+            }
+            debug!(height, next, "buffering certified payload ahead of a gap");
+            for (payload, epoch, proof) in members {
+                state.buffered.insert(payload.height(), (payload, epoch, Some(proof)));
+            }
+            return Ok(None);
+        }
+        if !Self::extends(&members[0].0, tip) {
+            warn!(height, "certified payload does not extend the latest");
+            return Ok(None);
+        }
+
+        let mut heights = Vec::new();
+        let mut members = members.into_iter();
+        while let Some((member, epoch, proof)) = members.next() {
+            let member_height = member.height();
+            if let Err(error) = self.store_proven(&member, epoch, Some(proof.clone())).await {
+                // The rest of the batch stays pending as a unit
+                if member_height < end {
+                    state.batches.insert(member_height, end);
+                    state.locks.insert(member_height, (epoch, proof));
+                    state
+                        .locks
+                        .extend(members.map(|(p, epoch, proof)| (p.height(), (epoch, proof))));
+                }
+                return Err(error);
+            }
+            state.relayed.remove(&member.digest());
+            Self::supersede_pending(&mut state, &member);
+            self.notify_certified(&mut state, &member, epoch);
+            heights.push(member_height);
+        }
+        heights.extend(self.drain_buffered(&mut state).await?.iter().map(Payload::height));
+        self.retain(&mut state, &heights).await;
+        Ok(Some(payload))
+    }
+
+    /// Returns the heights of the pending batch `height` belongs to, if any.
+    fn batch_of(state: &PayloadState<P, K>, height: Height) -> Option<RangeInclusive<Height>> {
+        let (&start, &end) = state.batches.range(..=height).next_back()?;
+        (height <= end).then_some(start..=end)
+    }
+
+    /// Returns the local proposal with `digest`, with the epoch it was
+    /// proposed in.
+    pub async fn proposal(&self, digest: &P::Digest) -> Option<(P, Epoch)> {
+        let state = self.state.read().await;
+        state
+            .pending
+            .values()
+            .find(|pending| pending.payload.digest() == *digest)
+            .map(|pending| (pending.payload.clone(), pending.epoch))
     }
 
-    /// Returns a payload by height.
-    pub async fn get_by_height(&self, height: Height) -> Option<P> {
-        self.state.read().await.by_height.get(&height).cloned()
+    /// Returns the pending or relayed payload with `digest`.
+    pub async fn payload(&self, digest: &P::Digest) -> Option<P> {
+        let state = self.state.read().await;
+        state
+            .pending
+            .values()
+            .map(|pending| &pending.payload)
+            .chain(state.relayed.values().map(|(_, payload)| payload))
+            .find(|payload| payload.digest() == *digest)
+            .cloned()
     }
 
-    /// Submits a payload for proposal.
+    /// Certifies a payload directly in `epoch` (for validators receiving
+    /// certified payloads).
     ///
-    /// This is called by the conductor when acting as sequencer.
-    /// Returns a receiver that will yield the digest once the proposal
-    /// is ready to be broadcast.
-    pub async fn submit_proposal(
-        &self,
-        payload: P,
-        threshold: usize,
-    ) -> oneshot::Receiver<P::Digest> {
-        let (tx, rx) = oneshot::channel();
-        let digest = payload.digest();
-
+    /// Payloads are applied strictly in height order: one that arrives ahead
+    /// of a missing height is buffered until the gap is filled, see
+    /// [`PayloadAutomaton::missing_heights`]. Certifying an already certified
+    /// payload again is a no-op. A different local proposal pending at an
+    /// applied height is discarded along with every proposal above it, and
+    /// their receipts fail with [`ConductorError::Superseded`]. Returns the
+    /// payloads applied as a result, in height order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConductorError::InvalidHeight`] if a different payload is
+    /// already certified at its height or it is too far ahead of the latest
+    /// certified payload to buffer, [`ConductorError::ParentMismatch`]
+    /// if it does not extend the latest certified payload, or
+    /// [`ConductorError::Store`] if it could not be stored.
+    pub async fn certify(&self, payload: P, epoch: Epoch) -> Result<Vec<P>, ConductorError> {
         let mut state = self.state.write().await;
-        state.pending = Some(PendingPayload::new(payload, threshold));
-        state.pending_proposal = Some(tx);
+        let height = payload.height();
+        let tip = self.store.latest().await.map(|p| (p.height(), p.digest()));
+        let next = tip.map_or(0, |(height, _)| height + 1);
 
-        // Immediately send the digest - the payload is ready for broadcast
-        if let Some(sender) = state.pending_proposal.take() {
-            let _ = sender.send(digest);
+        if height < next {
+            let certified = self.store.get_by_height(height).await;
+            if certified.is_some_and(|certified| certified.digest() == payload.digest()) {
+                return Ok(Vec::new());
+            }
+            return Err(ConductorError::InvalidHeight { expected: next, got: height });
+        }
+        if height > next {
+            if height - next >= BUFFER_WINDOW {
+                return Err(ConductorError::InvalidHeight { expected: next, got: height });
+            }
+            debug!(height, next, "buffering certified payload ahead of a gap");
+            state.buffered.insert(height, (payload, epoch, None));
+            return Ok(Vec::new());
+        }
+        if !Self::extends(&payload, tip) {
+            return Err(Self::extension_error(&payload, tip).into());
         }
 
-        rx
+        self.store.store(&payload).await?;
+        Self::supersede_pending(&mut state, &payload);
+        self.notify_certified(&mut state, &payload, epoch);
+        let mut applied = vec![payload];
+        applied.extend(self.drain_buffered(&mut state).await?);
+        let heights: Vec<_> = applied.iter().map(Payload::height).collect();
+        self.retain(&mut state, &heights).await;
+        Ok(applied)
     }
 
-    /// Records an acknowledgment for the pending payload.
+    /// Returns the ranges of heights missing below buffered out-of-order
+    /// payloads, in height order.
     ///
-    /// Returns the certified payload if quorum is reached.
-    pub async fn acknowledge(&self) -> Option<P> {
-        let mut state = self.state.write().await;
-
-        if let Some(ref mut pending) = state.pending {
-            pending.acknowledge();
-
-            if pending.is_certified() {
-                let payload = pending.payload.clone();
-                let height = payload.height();
-
-                state.by_height.insert(height, payload.clone());
-                state.latest_certified = Some(payload.clone());
-                state.pending = None;
-
-                return Some(payload);
+    /// Each range can be requested from a peer, e.g. with a
+    /// [`SyncSource`].
+    pub async fn missing_heights(&self) -> Vec<RangeInclusive<Height>> {
+        let state = self.state.read().await;
+        let mut next = self.store.latest().await.map_or(0, |latest| latest.height() + 1);
+        let mut missing = Vec::new();
+        for &height in state.buffered.keys() {
+            if height > next {
+                missing.push(next..=height - 1);
             }
+            next = next.max(height + 1);
         }
+        missing
+    }
 
-        None
+    /// Applies buffered payloads that now extend the latest certified
+    /// payload, returning them in height order.
+    ///
+    /// Buffered payloads at or below the latest certified height, or that do
+    /// not link to their predecessor, are discarded.
+    async fn drain_buffered(&self, state: &mut PayloadState<P, K>) -> Result<Vec<P>, StoreError> {
+        let mut applied = Vec::new();
+        loop {
+            let tip = self.store.latest().await.map(|p| (p.height(), p.digest()));
+            let next = tip.map_or(0, |(height, _)| height + 1);
+            state.buffered = state.buffered.split_off(&next);
+            let Some((payload, epoch, proof)) = state.buffered.remove(&next) else {
+                return Ok(applied);
+            };
+            if !Self::extends(&payload, tip) {
+                warn!(height = next, "discarding buffered payload that does not extend the latest");
+                continue;
+            }
+            self.store_proven(&payload, epoch, proof).await?;
+            Self::supersede_pending(state, &payload);
+            state.relayed.retain(|_, (_, relayed)| relayed.height() > next);
+            self.notify_certified(state, &payload, epoch);
+            applied.push(payload);
+        }
     }
 
-    /// Certifies a payload directly (for validators receiving certified payloads).
-    pub async fn certify(&self, payload: P) {
+    /// Records a payload relayed by `sender` in `epoch`.
+    ///
+    /// Chunks carrying the payload's digest can then be verified, including
+    /// any verification already waiting for it. Payloads at or below the
+    /// latest certified height are not buffered. A sender that relays two
+    /// different payloads at the same epoch and height is reported as an
+    /// [`Equivocation`].
+    pub async fn receive(&self, sender: K, epoch: Epoch, payload: P) {
         let mut state = self.state.write().await;
-        let height = payload.height();
-
-        state.by_height.insert(height, payload.clone());
+        let digest = payload.digest();
 
-        // Update latest if this is newer
-        let should_update =
-            state.latest_certified.as_ref().map(|p| payload.height() > p.height()).unwrap_or(true);
+        for (sequencer, tx) in state.verifying.remove(&digest).unwrap_or_default() {
+            let valid = self.check(&sequencer, Some(&sender), &payload).await;
+            let _ = tx.send(valid);
+        }
 
-        if should_update {
-            state.latest_certified = Some(payload);
+        let certified = self.store.latest().await.map(|p| p.height());
+        state.relayed.retain(|_, (_, relayed)| Some(relayed.height()) > certified);
+        if Some(payload.height()) > certified {
+            // Bound the evidence kept for junk far-future payloads
+            let next = certified.map_or(0, |certified| certified + 1);
+            if payload.height() < next + BUFFER_WINDOW {
+                self.record_proposed(&mut state, epoch, &sender, &payload);
+            }
+            state.relayed.insert(digest, (sender, payload));
         }
     }
 
-    /// Validates a payload for correctness.
+    /// Checks a payload carried by a chunk from `sequencer`.
     ///
-    /// Checks:
-    /// - Height is sequential
-    /// - Parent digest matches (if provided)
-    pub async fn validate(&self, payload: &P) -> bool {
-        let state = self.state.read().await;
+    /// `relayer` is the peer the payload was received from, or `None` for the
+    /// local node's own proposals. A payload must be relayed by the chunk's
+    /// sequencer, extend the latest certified payload and pass the
+    /// [validator](Self::with_validator). A digest already certified is only
+    /// accepted as the latest certified payload, so older digests cannot be
+    /// replayed.
+    async fn check(&self, sequencer: &K, relayer: Option<&K>, payload: &P) -> bool {
+        if relayer.is_some_and(|relayer| relayer != sequencer) {
+            debug!(height = payload.height(), "payload not relayed by the chunk's sequencer");
+            return false;
+        }
+
+        let latest = self.store.latest().await;
+        if self.store.get(&payload.digest()).await.is_some() {
+            let is_latest = latest.is_some_and(|latest| latest.digest() == payload.digest());
+            if !is_latest {
+                warn!(height = payload.height(), "rejecting replayed certified digest");
+            }
+            return is_latest;
+        }
+
+        match self.validate_against(payload, latest.as_ref()) {
+            Ok(()) => true,
+            Err(error) => {
+                warn!(height = payload.height(), %error, "rejecting invalid payload");
+                false
+            }
+        }
+    }
 
+    /// Returns whether `payload` directly extends `tip`.
+    fn extends(payload: &P, tip: Option<(Height, P::Digest)>) -> bool {
         // Check height is sequential
-        let expected_height = state.latest_certified.as_ref().map(|p| p.height() + 1).unwrap_or(0);
+        let expected_height = tip.map(|(height, _)| height + 1).unwrap_or(0);
 
         if payload.height() != expected_height {
             return false;
@@ -212,7 +1386,7 @@ impl<P: Payload, K> PayloadAutomaton<P, K> {
 
         // Check parent if provided
         if let Some(parent_digest) = payload.parent() {
-            let expected_parent = state.latest_certified.as_ref().map(|p| p.digest());
+            let expected_parent = tip.map(|(_, digest)| digest);
             if Some(parent_digest) != expected_parent {
                 return false;
             }
@@ -220,111 +1394,171 @@ impl<P: Payload, K> PayloadAutomaton<P, K> {
 
         true
     }
+
+    /// Returns the error for `payload` not extending `tip`.
+    fn extension_error(payload: &P, tip: Option<(Height, P::Digest)>) -> ValidationError {
+        let expected = tip.map_or(0, |(height, _)| height + 1);
+        if payload.height() != expected {
+            return ValidationError::InvalidHeight { expected, got: payload.height() };
+        }
+        let describe = |digest: Option<P::Digest>| {
+            digest.map_or_else(|| "none".to_string(), |digest| hex(digest.as_ref()))
+        };
+        ValidationError::ParentMismatch {
+            expected: describe(tip.map(|(_, digest)| digest)),
+            got: describe(payload.parent()),
+        }
+    }
+
+    /// Checks that `payload` extends `parent` and passes the
+    /// [validator](Self::with_validator).
+    fn validate_against(&self, payload: &P, parent: Option<&P>) -> Result<(), ValidationError> {
+        let tip = parent.map(|parent| (parent.height(), parent.digest()));
+        if !Self::extends(payload, tip) {
+            return Err(Self::extension_error(payload, tip));
+        }
+        self.validator.validate(payload, parent).map_err(ValidationError::Rejected)
+    }
+
+    /// Validates a payload for correctness.
+    ///
+    /// Checks against the newest in-flight or certified payload:
+    /// - Height is sequential
+    /// - Parent digest matches (if provided)
+    /// - The [validator](Self::with_validator) accepts it
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValidationError::InvalidHeight`] or
+    /// [`ValidationError::ParentMismatch`] if the payload does not extend the
+    /// newest payload, or [`ValidationError::Rejected`] with the validator's
+    /// reason if it is rejected.
+    pub async fn validate(&self, payload: &P) -> Result<(), ValidationError> {
+        let state = self.state.read().await;
+        let parent = self.tip_payload(&state).await;
+        self.validate_against(payload, parent.as_ref())
+    }
+}
+
+/// Serves the automaton's certified history, e.g. to catch up a local
+/// conductor from another in the same process.
+impl<P, K, St> SyncSource<P, K> for PayloadAutomaton<P, K, St>
+where
+    P: Payload,
+    K: PublicKey,
+    St: PayloadStore<P>,
+{
+    async fn tip(&self) -> Result<Option<Height>, SyncError> {
+        Ok(self.latest().await.map(|payload| payload.height()))
+    }
+
+    async fn fetch(&self, from: Height, to: Height) -> Result<Vec<SyncEntry<P, K>>, SyncError> {
+        let mut entries = Vec::new();
+        for height in from..=to {
+            let Some(payload) = self.store.get_by_height(height).await else {
+                break;
+            };
+            entries.push((payload, self.certificate(height).await));
+        }
+        Ok(entries)
+    }
 }
 
 /// Implementation of commonware's Automaton trait.
 ///
 /// This bridges our generic payload abstraction with commonware's
 /// consensus engine.
-impl<P, K> Automaton for PayloadAutomaton<P, K>
+impl<P, K, St> Automaton for PayloadAutomaton<P, K, St>
 where
     P: Payload,
-    K: Clone + Send + Sync + 'static,
+    K: PublicKey,
+    St: PayloadStore<P>,
 {
     type Context = PayloadContext<K>;
     type Digest = P::Digest;
 
     async fn genesis(&mut self, _epoch: ConsensusEpoch) -> Self::Digest {
-        self.state
-            .read()
-            .await
-            .latest_certified
-            .as_ref()
-            .map(|p| p.digest())
-            .unwrap_or(<P::Digest as DigestTrait>::EMPTY)
+        self.store.latest().await.map(|p| p.digest()).unwrap_or(<P::Digest as DigestTrait>::EMPTY)
     }
 
-    async fn propose(&mut self, _ctx: Self::Context) -> fc_oneshot::Receiver<Self::Digest> {
+    async fn propose(&mut self, ctx: Self::Context) -> fc_oneshot::Receiver<Self::Digest> {
         let (tx, rx) = fc_oneshot::channel();
 
-        // Check if we have a pending payload to propose
-        let state = self.state.read().await;
-        if let Some(ref pending) = state.pending {
-            let digest = pending.payload.digest();
-            let _ = tx.send(digest);
+        // The engine only asks for a new chunk once the previous one is
+        // certified, so the oldest pending payload is the next to propose
+        let mut state = self.state.write().await;
+        if let Some(pending) = state.pending.values().next() {
+            let _ = tx.send(pending.payload.digest());
+        } else {
+            // Park the request until the next payload is submitted
+            debug!(height = %ctx.height, "no pending payload, waiting for submission");
+            state.waiting = Some(tx);
         }
-        // If no pending payload, the receiver will be dropped,
-        // signaling that no proposal is ready
 
         rx
     }
 
     async fn verify(
         &mut self,
-        _ctx: Self::Context,
+        ctx: Self::Context,
         digest: Self::Digest,
     ) -> fc_oneshot::Receiver<bool> {
         let (tx, rx) = fc_oneshot::channel();
+        let mut state = self.state.write().await;
 
-        // Verify the digest corresponds to a known or valid payload
-        let state = self.state.read().await;
+        // Resolve the full payload: our own proposals, then relayed payloads,
+        // then certified payloads
+        let own = state.pending.values().find(|pending| pending.payload.digest() == digest);
+        let resolved = match (own, state.relayed.get(&digest)) {
+            (Some(pending), _) => Some((None, pending.payload.clone())),
+            (None, Some((sender, payload))) => Some((Some(sender.clone()), payload.clone())),
+            (None, None) => self.store.get(&digest).await.map(|payload| (None, payload)),
+        };
 
-        // Check pending payload, or fall back to checking certified payloads
-        let valid = state.pending.as_ref().map_or_else(
-            || state.by_height.values().any(|p| p.digest() == digest),
-            |pending| pending.payload.digest() == digest,
-        );
+        match resolved {
+            Some((relayer, payload)) => {
+                let valid = self.check(&ctx.sequencer, relayer.as_ref(), &payload).await;
+                let _ = tx.send(valid);
+            }
+            None => {
+                // Park until the payload is relayed
+                debug!(height = %ctx.height, "payload unknown, waiting for relay");
+                state.verifying.retain(|_, waiting| {
+                    waiting.retain(|(_, tx)| !tx.is_canceled());
+                    !waiting.is_empty()
+                });
+                state.verifying.entry(digest).or_default().push((ctx.sequencer, tx));
+            }
+        }
 
-        let _ = tx.send(valid);
         rx
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use commonware_cryptography::{Hasher as _, sha256};
+    use commonware_codec::{Decode as _, Encode as _};
+    use commonware_consensus::types::Height as ConsensusHeight;
+    use commonware_cryptography::{Hasher as _, Signer as _, ed25519, sha256};
 
     use super::*;
+    use crate::{
+        store::{FilePayloadStore, FileStoreConfig},
+        test_utils::TestPayload,
+    };
 
-    #[derive(Clone, Debug, PartialEq)]
-    struct TestPayload {
-        data: Vec<u8>,
-        height: Height,
-        parent: Option<sha256::Digest>,
-    }
-
-    impl Payload for TestPayload {
-        type Digest = sha256::Digest;
-
-        fn digest(&self) -> Self::Digest {
-            let mut hasher = sha256::Sha256::new();
-            hasher.update(&self.height.to_le_bytes());
-            hasher.update(&self.data);
-            hasher.finalize()
-        }
-
-        fn height(&self) -> Height {
-            self.height
-        }
-
-        fn parent(&self) -> Option<Self::Digest> {
-            self.parent
-        }
+    type TestKey = ed25519::PublicKey;
 
-        fn encode(&self) -> Vec<u8> {
-            let mut buf = Vec::new();
-            buf.extend_from_slice(&self.height.to_le_bytes());
-            buf.extend_from_slice(&self.data);
-            buf
-        }
+    fn ack(seed: u64, payload: &TestPayload) -> Acknowledgment<TestKey, sha256::Digest> {
+        let signer = ed25519::PrivateKey::from_seed(seed);
+        Acknowledgment::sign(&signer, 0, payload.height(), payload.digest())
+    }
 
-        fn decode(bytes: &[u8]) -> Option<Self> {
-            if bytes.len() < 8 {
-                return None;
-            }
-            let height = u64::from_le_bytes(bytes[..8].try_into().ok()?);
-            let data = bytes[8..].to_vec();
-            Some(Self { data, height, parent: None })
+    fn chunk_proof(payload: &TestPayload) -> ChunkProof<TestKey> {
+        ChunkProof {
+            sequencer: ed25519::PrivateKey::from_seed(0).public_key(),
+            height: payload.height(),
+            certificate: payload.digest().to_vec(),
         }
     }
 
@@ -333,7 +1567,7 @@ mod tests {
         let genesis = TestPayload { data: vec![1, 2, 3], height: 0, parent: None };
         let genesis_digest = genesis.digest();
 
-        let mut automaton: PayloadAutomaton<TestPayload, String> =
+        let mut automaton: PayloadAutomaton<TestPayload, TestKey> =
             PayloadAutomaton::with_genesis(genesis);
 
         let result = automaton.genesis(ConsensusEpoch::new(0)).await;
@@ -342,7 +1576,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_automaton_empty_genesis() {
-        let mut automaton: PayloadAutomaton<TestPayload, String> = PayloadAutomaton::new();
+        let mut automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
 
         let result = automaton.genesis(ConsensusEpoch::new(0)).await;
         assert_eq!(result, sha256::Digest::EMPTY);
@@ -350,49 +1584,64 @@ mod tests {
 
     #[tokio::test]
     async fn test_submit_and_acknowledge() {
-        let automaton: PayloadAutomaton<TestPayload, String> = PayloadAutomaton::new();
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
 
         let payload = TestPayload { data: vec![1, 2, 3], height: 0, parent: None };
 
         // Submit with threshold of 2
-        let rx = automaton.submit_proposal(payload.clone(), 2).await;
-        let digest = rx.await.unwrap();
+        let receipt = automaton.submit_proposal(payload.clone(), 0, 2).await.unwrap();
+        let digest = *receipt.digest();
         assert_eq!(digest, payload.digest());
 
         // First ack - not certified yet
-        assert!(automaton.acknowledge().await.is_none());
+        assert!(automaton.acknowledge(ack(1, &payload)).await.unwrap().is_empty());
+
+        // Duplicate ack from the same validator is ignored
+        assert!(automaton.acknowledge(ack(1, &payload)).await.unwrap().is_empty());
 
         // Second ack - certified
-        let certified = automaton.acknowledge().await;
-        assert!(certified.is_some());
-        assert_eq!(certified.unwrap(), payload);
+        let certified = automaton.acknowledge(ack(2, &payload)).await.unwrap();
+        assert_eq!(certified, vec![payload.clone()]);
+
+        // The receipt resolves with where the payload was certified
+        assert_eq!(receipt.await, Ok(Committed { height: 0, digest, epoch: 0 }));
+
+        // Certified payload was written through the store
+        assert_eq!(automaton.store().get(&digest).await, Some(payload));
+
+        // Alongside a certificate carrying both signatures
+        let certificate = automaton.certificate(0).await.unwrap();
+        assert_eq!(certificate.digest, digest);
+        let validators = [1, 2].map(|seed| ed25519::PrivateKey::from_seed(seed).public_key());
+        assert!(certificate.verify(&validators, 2));
     }
 
     #[tokio::test]
     async fn test_validate_sequential_heights() {
         let genesis = TestPayload { data: vec![1], height: 0, parent: None };
-        let automaton: PayloadAutomaton<TestPayload, String> =
+        let automaton: PayloadAutomaton<TestPayload, TestKey> =
             PayloadAutomaton::with_genesis(genesis);
 
         // Valid next payload
         let valid = TestPayload { data: vec![2], height: 1, parent: None };
-        assert!(automaton.validate(&valid).await);
+        assert!(automaton.validate(&valid).await.is_ok());
 
         // Invalid height (skipped)
         let invalid = TestPayload { data: vec![3], height: 5, parent: None };
-        assert!(!automaton.validate(&invalid).await);
+        let result = automaton.validate(&invalid).await;
+        assert_eq!(result, Err(ValidationError::InvalidHeight { expected: 1, got: 5 }));
     }
 
     #[tokio::test]
     async fn test_validate_parent_digest() {
         let genesis = TestPayload { data: vec![1], height: 0, parent: None };
         let genesis_digest = genesis.digest();
-        let automaton: PayloadAutomaton<TestPayload, String> =
+        let automaton: PayloadAutomaton<TestPayload, TestKey> =
             PayloadAutomaton::with_genesis(genesis);
 
         // Valid parent
         let valid = TestPayload { data: vec![2], height: 1, parent: Some(genesis_digest) };
-        assert!(automaton.validate(&valid).await);
+        assert!(automaton.validate(&valid).await.is_ok());
 
         // Invalid parent - create a different digest
         let invalid_parent = {
@@ -401,33 +1650,739 @@ mod tests {
             hasher.finalize()
         };
         let invalid = TestPayload { data: vec![3], height: 1, parent: Some(invalid_parent) };
-        assert!(!automaton.validate(&invalid).await);
+        let result = automaton.validate(&invalid).await;
+        assert_eq!(
+            result,
+            Err(ValidationError::ParentMismatch {
+                expected: hex(genesis_digest.as_ref()),
+                got: hex(invalid_parent.as_ref()),
+            })
+        );
     }
 
     #[tokio::test]
     async fn test_certify_directly() {
-        let automaton: PayloadAutomaton<TestPayload, String> = PayloadAutomaton::new();
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
 
         let payload = TestPayload { data: vec![1, 2, 3], height: 0, parent: None };
 
-        automaton.certify(payload.clone()).await;
+        automaton.certify(payload.clone(), 0).await.unwrap();
 
         let latest = automaton.latest().await;
         assert_eq!(latest, Some(payload));
     }
 
+    #[tokio::test]
+    async fn test_certify_buffers_out_of_order_payloads() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let mut chain = Vec::new();
+        let mut parent = None;
+        for height in 0..5 {
+            let payload = TestPayload { data: vec![height as u8], height, parent };
+            parent = Some(payload.digest());
+            chain.push(payload);
+        }
+
+        automaton.certify(chain[0].clone(), 0).await.unwrap();
+        assert!(automaton.certify(chain[2].clone(), 0).await.unwrap().is_empty());
+        assert!(automaton.certify(chain[4].clone(), 0).await.unwrap().is_empty());
+        assert_eq!(automaton.latest().await, Some(chain[0].clone()));
+        assert_eq!(automaton.next_height().await, 1);
+        assert_eq!(automaton.missing_heights().await, vec![1..=1, 3..=3]);
+
+        // Filling a gap applies the buffered payloads behind it
+        let applied = automaton.certify(chain[1].clone(), 0).await.unwrap();
+        assert_eq!(applied, chain[1..3].to_vec());
+        assert_eq!(automaton.missing_heights().await, vec![3..=3]);
+
+        // Already certified payloads are ignored, conflicting ones rejected
+        assert!(automaton.certify(chain[1].clone(), 0).await.unwrap().is_empty());
+        let conflicting = TestPayload { data: vec![9], ..chain[1].clone() };
+        assert!(matches!(
+            automaton.certify(conflicting, 0).await,
+            Err(ConductorError::InvalidHeight { expected: 3, got: 1 })
+        ));
+
+        let orphan = TestPayload { data: vec![9], height: 3, parent: Some(chain[0].digest()) };
+        assert!(matches!(
+            automaton.certify(orphan, 0).await,
+            Err(ConductorError::ParentMismatch { .. })
+        ));
+        assert_eq!(automaton.certify(chain[3].clone(), 0).await.unwrap(), chain[3..].to_vec());
+        assert!(automaton.missing_heights().await.is_empty());
+        assert_eq!(automaton.latest().await, Some(chain[4].clone()));
+    }
+
+    #[tokio::test]
+    async fn test_certify_rejects_payloads_beyond_buffer_window() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let far = TestPayload { data: vec![1], height: BUFFER_WINDOW, parent: None };
+        assert_eq!(
+            automaton.certify(far, 0).await,
+            Err(ConductorError::InvalidHeight { expected: 0, got: BUFFER_WINDOW })
+        );
+        assert!(automaton.missing_heights().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_certify_supersedes_conflicting_proposals() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        let r0 = automaton.submit_proposal(p0.clone(), 0, 2).await.unwrap();
+        let r1 = automaton.submit_proposal(p1.clone(), 0, 2).await.unwrap();
+
+        // A different payload certified at the first height orphans both
+        let other = TestPayload { data: vec![9], height: 0, parent: None };
+        automaton.certify(other, 0).await.unwrap();
+        assert_eq!(automaton.pending_count().await, 0);
+        assert_eq!(r0.await, Err(ConductorError::Superseded { height: 0 }));
+        assert_eq!(r1.await, Err(ConductorError::Superseded { height: 1 }));
+
+        // The orphaned proposal can no longer be certified
+        assert!(automaton.acknowledge(ack(1, &p1)).await.unwrap().is_empty());
+        assert_eq!(
+            automaton.certify_digest(&p1.digest(), 0, chunk_proof(&p1)).await.unwrap(),
+            None
+        );
+        assert_eq!(automaton.next_height().await, 1);
+    }
+
     #[tokio::test]
     async fn test_get_by_height() {
-        let automaton: PayloadAutomaton<TestPayload, String> = PayloadAutomaton::new();
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
 
         let p0 = TestPayload { data: vec![1], height: 0, parent: None };
         let p1 = TestPayload { data: vec![2], height: 1, parent: None };
 
-        automaton.certify(p0.clone()).await;
-        automaton.certify(p1.clone()).await;
+        automaton.certify(p0.clone(), 0).await.unwrap();
+        automaton.certify(p1.clone(), 0).await.unwrap();
+
+        assert_eq!(automaton.get_by_height(0).await, Ok(Some(p0)));
+        assert_eq!(automaton.get_by_height(1).await, Ok(Some(p1)));
+        assert_eq!(automaton.get_by_height(2).await, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn test_retention_keep_last() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> =
+            PayloadAutomaton::new().with_retention(RetentionPolicy::KeepLast(2));
+        let payloads: Vec<_> = (0..4)
+            .map(|height| TestPayload { data: vec![height as u8], height, parent: None })
+            .collect();
+        for payload in &payloads {
+            automaton.certify(payload.clone(), 0).await.unwrap();
+        }
+
+        assert_eq!(automaton.retained_from().await, 2);
+        assert_eq!(
+            automaton.get_by_height(1).await,
+            Err(ConductorError::Pruned { height: 1, retained_from: 2 })
+        );
+        assert_eq!(automaton.get_by_height(2).await, Ok(Some(payloads[2].clone())));
+        assert_eq!(automaton.get_by_height(4).await, Ok(None));
+        assert_eq!(automaton.latest().await, Some(payloads[3].clone()));
+    }
+
+    #[tokio::test]
+    async fn test_retention_keep_for() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new()
+            .with_retention(RetentionPolicy::KeepFor(std::time::Duration::ZERO));
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        automaton.certify(p0, 0).await.unwrap();
+        automaton.certify(p1.clone(), 0).await.unwrap();
+
+        // Everything but the latest payload has expired
+        assert_eq!(automaton.retained_from().await, 1);
+        assert!(matches!(automaton.get_by_height(0).await, Err(ConductorError::Pruned { .. })));
+        assert_eq!(automaton.get_by_height(1).await, Ok(Some(p1)));
+    }
+
+    #[tokio::test]
+    async fn test_retention_point_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = FileStoreConfig { max_segment_size: 1, sync_writes: false };
+        let payloads: Vec<_> = (0..4)
+            .map(|height| TestPayload { data: vec![height as u8], height, parent: None })
+            .collect();
+        {
+            let store = FilePayloadStore::open_with_config(dir.path(), config.clone()).unwrap();
+            let automaton: PayloadAutomaton<TestPayload, TestKey, _> =
+                PayloadAutomaton::with_store(store).with_retention(RetentionPolicy::KeepLast(2));
+            for payload in &payloads {
+                automaton.certify(payload.clone(), 0).await.unwrap();
+            }
+            assert_eq!(automaton.retained_from().await, 2);
+        }
+
+        let store = FilePayloadStore::open_with_config(dir.path(), config).unwrap();
+        let automaton: PayloadAutomaton<TestPayload, TestKey, _> =
+            PayloadAutomaton::with_store(store);
+        assert_eq!(automaton.retained_from().await, 2);
+        assert_eq!(
+            automaton.get_by_height(1).await,
+            Err(ConductorError::Pruned { height: 1, retained_from: 2 })
+        );
+        assert_eq!(automaton.snapshot().await.history, payloads[2..].to_vec());
+    }
+
+    /// A store that keeps everything, relying on the default `prune`.
+    #[derive(Clone, Default)]
+    struct KeepingStore(InMemoryPayloadStore<TestPayload>);
+
+    impl PayloadStore<TestPayload> for KeepingStore {
+        async fn store(&self, payload: &TestPayload) -> Result<(), StoreError> {
+            self.0.store(payload).await
+        }
+
+        async fn get(&self, digest: &sha256::Digest) -> Option<TestPayload> {
+            self.0.get(digest).await
+        }
+
+        async fn get_by_height(&self, height: Height) -> Option<TestPayload> {
+            self.0.get_by_height(height).await
+        }
+
+        async fn latest(&self) -> Option<TestPayload> {
+            self.0.latest().await
+        }
+
+        async fn store_certificate(
+            &self,
+            height: Height,
+            certificate: &[u8],
+        ) -> Result<(), StoreError> {
+            self.0.store_certificate(height, certificate).await
+        }
+
+        async fn get_certificate(&self, height: Height) -> Option<Vec<u8>> {
+            self.0.get_certificate(height).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retention_without_pruning_store() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey, KeepingStore> =
+            PayloadAutomaton::with_store(KeepingStore::default())
+                .with_retention(RetentionPolicy::KeepLast(1));
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        automaton.certify(p0.clone(), 0).await.unwrap();
+        automaton.certify(p1.clone(), 0).await.unwrap();
+
+        // Nothing was pruned, so the history is still served
+        assert_eq!(automaton.retained_from().await, 0);
+        assert_eq!(automaton.get_by_height(0).await, Ok(Some(p0.clone())));
+        assert_eq!(automaton.snapshot().await.history, vec![p0, p1]);
+    }
+
+    #[tokio::test]
+    async fn test_resumes_from_store() {
+        let p0 = TestPayload { data: vec![1], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![2], height: 1, parent: Some(p0.digest()) };
+        let store: InMemoryPayloadStore<TestPayload> = [p0, p1.clone()].into_iter().collect();
+
+        let automaton: PayloadAutomaton<TestPayload, TestKey, _> =
+            PayloadAutomaton::with_store(store);
+
+        assert_eq!(automaton.latest().await, Some(p1));
+        assert_eq!(automaton.next_height().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_pipelined_proposals_certify_in_order() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        let p2 = TestPayload { data: vec![2], height: 2, parent: Some(p1.digest()) };
+
+        for payload in [&p0, &p1, &p2] {
+            assert!(automaton.validate(payload).await.is_ok());
+            automaton.submit_proposal(payload.clone(), 0, 1).await.unwrap();
+        }
+        assert_eq!(automaton.next_height().await, 3);
+        assert_eq!(automaton.pending_count().await, 3);
+
+        // Quorum at a later height is held until its predecessors certify
+        assert!(automaton.acknowledge(ack(1, &p2)).await.unwrap().is_empty());
+        assert!(automaton.acknowledge(ack(1, &p1)).await.unwrap().is_empty());
+        assert_eq!(automaton.latest().await, None);
+
+        let certified = automaton.acknowledge(ack(1, &p0)).await.unwrap();
+        assert_eq!(certified, vec![p0, p1, p2.clone()]);
+        assert_eq!(automaton.latest().await, Some(p2));
+        assert_eq!(automaton.pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_batch_certifies_as_a_unit() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        let p2 = TestPayload { data: vec![2], height: 2, parent: Some(p1.digest()) };
+
+        // Batches must be non-empty and parent-linked
+        let result = automaton.submit_batch(vec![], 0, 2).await;
+        assert!(matches!(result, Err(ConductorError::ValidationFailed(_))));
+        let unlinked = TestPayload { data: vec![1], height: 1, parent: Some(p2.digest()) };
+        let result = automaton.submit_batch(vec![p0.clone(), unlinked], 0, 2).await;
+        assert!(matches!(result, Err(ConductorError::ParentMismatch { .. })));
+        let result = automaton.submit_batch(vec![p0.clone(), p2.clone()], 0, 2).await;
+        assert_eq!(result.err(), Some(ConductorError::InvalidHeight { expected: 1, got: 2 }));
+        assert_eq!(automaton.pending_count().await, 0);
+
+        let receipts = automaton.submit_batch(vec![p0.clone(), p1.clone()], 0, 2).await.unwrap();
+        automaton.submit_proposal(p2.clone(), 0, 2).await.unwrap();
+
+        // The head of the batch reaching quorum does not certify it alone
+        for seed in [1, 2] {
+            assert!(automaton.acknowledge(ack(seed, &p0)).await.unwrap().is_empty());
+        }
+        assert!(automaton.acknowledge(ack(1, &p1)).await.unwrap().is_empty());
+        assert_eq!(automaton.latest().await, None);
+
+        let certified = automaton.acknowledge(ack(2, &p1)).await.unwrap();
+        assert_eq!(certified, vec![p0, p1]);
+        for receipt in receipts {
+            assert!(receipt.await.is_ok());
+        }
+
+        // Payloads after the batch certify on their own
+        automaton.acknowledge(ack(1, &p2)).await.unwrap();
+        assert_eq!(automaton.acknowledge(ack(2, &p2)).await.unwrap(), vec![p2]);
+    }
+
+    #[tokio::test]
+    async fn test_engine_certifies_batch_as_a_unit() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        let p2 = TestPayload { data: vec![2], height: 2, parent: Some(p1.digest()) };
+        let p3 = TestPayload { data: vec![3], height: 3, parent: Some(p2.digest()) };
+        automaton.submit_proposal(p0.clone(), 0, 2).await.unwrap();
+        let receipts = automaton.submit_batch(vec![p1.clone(), p2.clone()], 0, 2).await.unwrap();
+        automaton.submit_proposal(p3.clone(), 0, 2).await.unwrap();
+
+        // A locked batch member is held while the rest of the batch is not
+        assert_eq!(
+            automaton.certify_digest(&p1.digest(), 0, chunk_proof(&p1)).await.unwrap(),
+            None
+        );
+        assert_eq!(
+            automaton.certify_digest(&p0.digest(), 0, chunk_proof(&p0)).await.unwrap(),
+            Some(p0)
+        );
+        assert_eq!(automaton.pending_count().await, 3);
+
+        // The last member applies the whole batch, with each member's proof
+        assert_eq!(
+            automaton.certify_digest(&p2.digest(), 0, chunk_proof(&p2)).await.unwrap(),
+            Some(p2.clone())
+        );
+        assert_eq!(automaton.latest().await, Some(p2));
+        assert_eq!(automaton.certificate(1).await.unwrap().chunk, Some(chunk_proof(&p1)));
+        for receipt in receipts {
+            assert!(receipt.await.is_ok());
+        }
+
+        // Payloads after the batch certify on their own
+        assert_eq!(
+            automaton.certify_digest(&p3.digest(), 0, chunk_proof(&p3)).await.unwrap(),
+            Some(p3)
+        );
+        assert_eq!(automaton.pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_acknowledge_ignores_wrong_digest() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        let other = TestPayload { data: vec![2], height: 0, parent: None };
+        automaton.submit_proposal(payload, 0, 1).await.unwrap();
+
+        assert!(automaton.acknowledge(ack(1, &other)).await.unwrap().is_empty());
+        assert_eq!(automaton.pending_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_when_window_full() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> =
+            PayloadAutomaton::new().with_max_pending(1);
+
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: None };
+        automaton.submit_proposal(p0, 0, 1).await.unwrap();
+
+        let result = automaton.submit_proposal(p1, 0, 1).await;
+        assert!(matches!(result, Err(ConductorError::WindowFull { limit: 1 })));
+    }
+
+    #[tokio::test]
+    async fn test_queue_status_and_wait_for_room() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> =
+            PayloadAutomaton::new().with_max_pending(2);
+        let status = automaton.queue_status().await;
+        assert_eq!((status.depth, status.capacity, status.oldest_pending_age), (0, 2, None));
+
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        automaton.submit_proposal(p0.clone(), 0, 1).await.unwrap();
+        automaton.submit_proposal(p1, 0, 1).await.unwrap();
+        let status = automaton.queue_status().await;
+        assert_eq!(status.depth, 2);
+        assert!(status.oldest_pending_age.is_some());
+
+        // Room for one more once the oldest payload is certified
+        let (_, certified) = tokio::join!(automaton.wait_for_room(1), async {
+            tokio::task::yield_now().await;
+            automaton.acknowledge(ack(1, &p0)).await.unwrap()
+        });
+        assert_eq!(certified, vec![p0]);
+        assert_eq!(automaton.queue_status().await.depth, 1);
+    }
+
+    #[tokio::test]
+    async fn test_propose_waits_for_submission() {
+        let mut automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let ctx = context(0);
+
+        let mut rx = automaton.propose(ctx).await;
+        assert!(matches!(rx.try_recv(), Ok(None)));
 
-        assert_eq!(automaton.get_by_height(0).await, Some(p0));
-        assert_eq!(automaton.get_by_height(1).await, Some(p1));
-        assert_eq!(automaton.get_by_height(2).await, None);
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        automaton.submit_proposal(payload.clone(), 0, 1).await.unwrap();
+        assert_eq!(rx.await.unwrap(), payload.digest());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_proposal() {
+        let mut automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let ctx = context(0);
+        assert!(!automaton.cancel_proposal().await);
+
+        let rx = automaton.propose(ctx.clone()).await;
+        assert!(automaton.cancel_proposal().await);
+        assert!(rx.await.is_err());
+
+        // The payload is not lost: the next request picks it up
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        automaton.submit_proposal(payload.clone(), 0, 1).await.unwrap();
+        assert_eq!(automaton.propose(ctx).await.await.unwrap(), payload.digest());
+    }
+
+    #[tokio::test]
+    async fn test_fence() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let first = TestPayload { data: vec![1], height: 0, parent: None };
+        let second = TestPayload { data: vec![2], height: 1, parent: Some(first.digest()) };
+        automaton.submit_proposal(first.clone(), 0, 1).await.unwrap();
+        automaton.submit_proposal(second.clone(), 0, 1).await.unwrap();
+
+        // Retained proposals move into the new epoch
+        assert!(automaton.fence(1, true).await.is_empty());
+        assert_eq!(automaton.state.read().await.pending[&0].epoch, 1);
+
+        // Dropped proposals are returned in height order
+        assert_eq!(automaton.fence(2, false).await, vec![first.clone(), second]);
+        assert_eq!(automaton.pending_count().await, 0);
+        assert!(automaton.fence(1, false).await.is_empty());
+        assert_eq!(automaton.fenced_epoch().await, 2);
+
+        // Proposals from fenced epochs are rejected
+        let result = automaton.submit_proposal(first.clone(), 1, 1).await;
+        assert!(matches!(result, Err(ConductorError::StaleEpoch { epoch: 1, current: 2 })));
+        automaton.submit_proposal(first, 2, 1).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_existing_receipt() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        assert!(automaton.existing_receipt(&payload).await.unwrap().is_none());
+
+        // Certified without a certificate, the epoch is still known
+        automaton.certify(payload.clone(), 3).await.unwrap();
+        let receipt = automaton.existing_receipt(&payload).await.unwrap().unwrap();
+        let committed = Committed { height: 0, digest: payload.digest(), epoch: 3 };
+        assert_eq!(receipt.await, Ok(committed));
+
+        let other = TestPayload { data: vec![2], height: 0, parent: None };
+        let result = automaton.existing_receipt(&other).await;
+        assert_eq!(result.err(), Some(ConductorError::InvalidHeight { expected: 1, got: 0 }));
+    }
+
+    #[tokio::test]
+    async fn test_receipts_fail_when_fenced_or_superseded() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let first = TestPayload { data: vec![1], height: 0, parent: None };
+        let receipt = automaton.submit_proposal(first.clone(), 0, 2).await.unwrap();
+        automaton.fence(1, false).await;
+        assert_eq!(receipt.await, Err(ConductorError::EpochChanged { height: 0, epoch: 1 }));
+
+        // A different payload certified at the height supersedes the proposal
+        let receipt = automaton.submit_proposal(first, 1, 2).await.unwrap();
+        let other = TestPayload { data: vec![2], height: 0, parent: None };
+        automaton.certify(other, 1).await.unwrap();
+        assert_eq!(receipt.await, Err(ConductorError::Superseded { height: 0 }));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_and_restore() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> =
+            PayloadAutomaton::new().with_retention(RetentionPolicy::KeepLast(2));
+        let mut parent = None;
+        for height in 0..3 {
+            let payload = TestPayload { data: vec![height as u8], height, parent };
+            parent = Some(payload.digest());
+            automaton.certify(payload, 0).await.unwrap();
+        }
+        automaton.fence(3, false).await;
+
+        // Only the retained history is captured
+        let snapshot = automaton.snapshot().await;
+        assert_eq!(snapshot.epoch, 3);
+        assert_eq!(snapshot.history.iter().map(Payload::height).collect::<Vec<_>>(), vec![1, 2]);
+        let decoded = Snapshot::decode_cfg(snapshot.encode().as_ref(), &2).unwrap();
+        assert_eq!(decoded, snapshot);
+
+        let restored: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        restored.restore(decoded).await.unwrap();
+        assert_eq!(restored.latest().await, snapshot.latest().cloned());
+        assert_eq!(restored.next_height().await, 3);
+        assert_eq!(restored.fenced_epoch().await, 3);
+        assert!(matches!(restored.get_by_height(0).await, Err(ConductorError::Pruned { .. })));
+
+        // Broken snapshots are rejected
+        let mut gapped = snapshot.clone();
+        gapped.history.remove(0);
+        gapped.history.insert(0, TestPayload { data: vec![9], height: 0, parent: None });
+        let empty = Snapshot { epoch: 0, history: Vec::new(), certificate: None };
+        let fresh: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        assert!(matches!(fresh.restore(gapped).await, Err(ConductorError::InvalidSnapshot(_))));
+        assert!(matches!(fresh.restore(empty).await, Err(ConductorError::InvalidSnapshot(_))));
+        assert_eq!(fresh.latest().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_acknowledge_ignores_far_future_heights() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let far = TestPayload { data: vec![1], height: BUFFER_WINDOW, parent: None };
+        let conflicting = TestPayload { data: vec![2], height: BUFFER_WINDOW, parent: None };
+
+        assert_eq!(automaton.acknowledge(ack(1, &far)).await, Ok(Vec::new()));
+        assert_eq!(automaton.acknowledge(ack(1, &conflicting)).await, Ok(Vec::new()));
+        assert!(automaton.equivocations().await.is_empty());
+        assert!(automaton.state.read().await.signed.is_empty());
+
+        // Heights within the window are still tracked
+        let near = TestPayload { data: vec![1], height: BUFFER_WINDOW - 1, parent: None };
+        automaton.acknowledge(ack(1, &near)).await.unwrap();
+        assert_eq!(automaton.state.read().await.signed.len(), 1);
+    }
+
+    fn context(seed: u64) -> PayloadContext<TestKey> {
+        PayloadContext {
+            sequencer: ed25519::PrivateKey::from_seed(seed).public_key(),
+            height: ConsensusHeight::zero(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_receive_reports_sequencer_equivocation() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let mut equivocations = automaton.subscribe_equivocations();
+        let sequencer = context(1).sequencer;
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        let conflicting = TestPayload { data: vec![2], height: 0, parent: None };
+
+        // Relaying the same payload twice is not a conflict, nor is relaying
+        // another payload in a different epoch
+        automaton.receive(sequencer.clone(), 3, payload.clone()).await;
+        automaton.receive(sequencer.clone(), 3, payload.clone()).await;
+        automaton.receive(sequencer.clone(), 4, conflicting.clone()).await;
+        assert!(automaton.equivocations().await.is_empty());
+
+        // Evidence is kept under the epoch the payloads were relayed in
+        automaton.receive(sequencer.clone(), 3, conflicting.clone()).await;
+        let equivocation = Equivocation::Sequencer {
+            sequencer,
+            epoch: 3,
+            height: 0,
+            first: payload.digest(),
+            second: conflicting.digest(),
+        };
+        assert_eq!(equivocations.recv().await.unwrap(), equivocation);
+        assert_eq!(automaton.equivocations().await, vec![equivocation]);
+    }
+
+    #[tokio::test]
+    async fn test_verify_waits_for_relayed_payload() {
+        let genesis = TestPayload { data: vec![0], height: 0, parent: None };
+        let mut automaton: PayloadAutomaton<TestPayload, TestKey> =
+            PayloadAutomaton::with_genesis(genesis.clone());
+        let payload = TestPayload { data: vec![1], height: 1, parent: Some(genesis.digest()) };
+
+        let mut rx = automaton.verify(context(1), payload.digest()).await;
+        assert!(matches!(rx.try_recv(), Ok(None)));
+
+        automaton.receive(context(1).sequencer, 0, payload.clone()).await;
+        assert!(rx.await.unwrap());
+
+        // Already relayed payloads verify immediately
+        assert!(automaton.verify(context(1), payload.digest()).await.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_rejects_invalid_payloads() {
+        let genesis = TestPayload { data: vec![0], height: 0, parent: None };
+        let mut automaton: PayloadAutomaton<TestPayload, TestKey> =
+            PayloadAutomaton::with_genesis(genesis.clone());
+
+        // Relayed by someone other than the chunk's sequencer
+        let payload = TestPayload { data: vec![1], height: 1, parent: Some(genesis.digest()) };
+        automaton.receive(context(2).sequencer, 0, payload.clone()).await;
+        assert!(!automaton.verify(context(1), payload.digest()).await.await.unwrap());
+
+        // Does not extend the certified tip
+        let skipped = TestPayload { data: vec![2], height: 2, parent: None };
+        automaton.receive(context(1).sequencer, 0, skipped.clone()).await;
+        assert!(!automaton.verify(context(1), skipped.digest()).await.await.unwrap());
+
+        let orphan = TestPayload { data: vec![3], height: 1, parent: Some(skipped.digest()) };
+        automaton.receive(context(1).sequencer, 0, orphan.clone()).await;
+        assert!(!automaton.verify(context(1), orphan.digest()).await.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_runs_validator() {
+        let genesis = TestPayload { data: vec![0], height: 0, parent: None };
+        let mut automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::with_genesis(
+            genesis.clone(),
+        )
+        .with_validator(|payload: &TestPayload, parent: Option<&TestPayload>| {
+            if parent.is_some_and(|parent| payload.data.len() <= parent.data.len()) {
+                return Err("data must grow".to_string());
+            }
+            Ok(())
+        });
+
+        let rejected = TestPayload { data: vec![1], height: 1, parent: Some(genesis.digest()) };
+        let result = automaton.validate(&rejected).await;
+        assert_eq!(result, Err(ValidationError::Rejected("data must grow".to_string())));
+        automaton.receive(context(1).sequencer, 0, rejected.clone()).await;
+        assert!(!automaton.verify(context(1), rejected.digest()).await.await.unwrap());
+
+        let accepted = TestPayload { data: vec![1, 2], height: 1, parent: Some(genesis.digest()) };
+        assert_eq!(automaton.validate(&accepted).await, Ok(()));
+        automaton.receive(context(1).sequencer, 0, accepted.clone()).await;
+        assert!(automaton.verify(context(1), accepted.digest()).await.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_rejects_replayed_digests() {
+        let genesis = TestPayload { data: vec![0], height: 0, parent: None };
+        let mut automaton: PayloadAutomaton<TestPayload, TestKey> =
+            PayloadAutomaton::with_genesis(genesis.clone());
+        assert!(automaton.verify(context(1), genesis.digest()).await.await.unwrap());
+
+        let next = TestPayload { data: vec![1], height: 1, parent: Some(genesis.digest()) };
+        automaton.certify(next.clone(), 0).await.unwrap();
+        assert!(!automaton.verify(context(1), genesis.digest()).await.await.unwrap());
+        assert!(automaton.verify(context(1), next.digest()).await.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_own_proposal() {
+        let mut automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        automaton.submit_proposal(p0.clone(), 0, 1).await.unwrap();
+        automaton.submit_proposal(p1.clone(), 0, 1).await.unwrap();
+
+        // Only the oldest proposal extends the certified tip
+        assert!(automaton.verify(context(1), p0.digest()).await.await.unwrap());
+        assert!(!automaton.verify(context(1), p1.digest()).await.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_certify_digest() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        automaton.submit_proposal(payload.clone(), 0, 2).await.unwrap();
+
+        let unknown = TestPayload { data: vec![2], height: 0, parent: None };
+        assert_eq!(
+            automaton.certify_digest(&unknown.digest(), 0, chunk_proof(&unknown)).await.unwrap(),
+            None
+        );
+
+        let certified =
+            automaton.certify_digest(&payload.digest(), 0, chunk_proof(&payload)).await.unwrap();
+        assert_eq!(certified, Some(payload.clone()));
+        assert_eq!(automaton.pending_count().await, 0);
+        assert_eq!(automaton.latest().await, Some(payload.clone()));
+
+        // The engine's proof is stored as the payload's certificate
+        let certificate = automaton.certificate(0).await.unwrap();
+        assert_eq!(certificate.digest, payload.digest());
+        assert!(certificate.signatures.is_empty());
+        assert_eq!(certificate.chunk, Some(chunk_proof(&payload)));
+    }
+
+    #[tokio::test]
+    async fn test_certify_relayed_digest() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        automaton.receive(context(1).sequencer, 0, p0.clone()).await;
+        automaton.receive(context(1).sequencer, 0, p1.clone()).await;
+        assert_eq!(automaton.payload(&p1.digest()).await, Some(p1.clone()));
+
+        // A payload ahead of the latest is buffered until the gap is filled
+        assert_eq!(
+            automaton.certify_digest(&p1.digest(), 0, chunk_proof(&p1)).await.unwrap(),
+            None
+        );
+        assert_eq!(automaton.latest().await, None);
+        assert_eq!(automaton.missing_heights().await, vec![0..=0]);
+        assert_eq!(
+            automaton.certify_digest(&p0.digest(), 0, chunk_proof(&p0)).await.unwrap(),
+            Some(p0.clone())
+        );
+        assert_eq!(automaton.get_by_height(0).await, Ok(Some(p0)));
+        assert_eq!(automaton.latest().await, Some(p1.clone()));
+        assert_eq!(automaton.payload(&p1.digest()).await, None);
+
+        // The buffered payload keeps its proof
+        let certificate = automaton.certificate(1).await.unwrap();
+        assert_eq!(certificate.chunk, Some(chunk_proof(&p1)));
+    }
+
+    #[tokio::test]
+    async fn test_certify_digest_in_height_order() {
+        let automaton: PayloadAutomaton<TestPayload, TestKey> = PayloadAutomaton::new();
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: Some(p0.digest()) };
+        automaton.submit_proposal(p0.clone(), 0, 2).await.unwrap();
+        automaton.submit_proposal(p1.clone(), 0, 2).await.unwrap();
+
+        // A lock on the second proposal does not leave a hole
+        assert_eq!(
+            automaton.certify_digest(&p1.digest(), 0, chunk_proof(&p1)).await.unwrap(),
+            None
+        );
+        assert_eq!(automaton.latest().await, None);
+        assert_eq!(automaton.pending_count().await, 2);
+
+        assert_eq!(
+            automaton.certify_digest(&p0.digest(), 0, chunk_proof(&p0)).await.unwrap(),
+            Some(p0)
+        );
+        assert_eq!(automaton.latest().await, Some(p1));
+        assert_eq!(automaton.pending_count().await, 0);
     }
 }
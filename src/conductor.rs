@@ -3,16 +3,54 @@
 //! The [`Conductor`] is the main entry point for the arturo consensus layer.
 //! It orchestrates payload ordering, certification, and epoch management.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+    marker::PhantomData,
+    ops::RangeInclusive,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
 
-use commonware_cryptography::Signer;
-use tokio::sync::RwLock;
+use commonware_codec::Decode as _;
+use commonware_consensus::{
+    ordered_broadcast::{
+        self,
+        scheme::Scheme,
+        types::{Chunk, ChunkSigner, ChunkVerifier, Lock},
+    },
+    types::{Epoch as ConsensusEpoch, EpochDelta, Height as ConsensusHeight, HeightDelta},
+};
+use commonware_cryptography::{
+    PublicKey, Signer,
+    certificate::{self, Scheme as _},
+};
+use commonware_p2p::{Receiver, Sender};
+use commonware_parallel::Sequential;
+use commonware_runtime::{Clock, Handle, Metrics, Spawner, Storage, buffer::PoolRef};
+use futures::{StreamExt as _, stream};
+use rand_core::CryptoRngCore;
+use tokio::{
+    sync::{RwLock, broadcast::error::RecvError, oneshot},
+    task::JoinHandle,
+};
 use tracing::{debug, info, warn};
 
 use crate::{
-    automaton::PayloadAutomaton,
-    traits::{EpochManager, EpochStream, Payload},
-    types::{ConductorError, EpochChange, TransferError},
+    automaton::{DEFAULT_CERTIFIED_CAPACITY, DEFAULT_MAX_PENDING, PayloadAutomaton},
+    engine::{EngineConfig, EpochMonitor, PayloadRelay, PayloadReporter},
+    providers::EpochSequencersProvider,
+    receipt::CommitReceipt,
+    store::InMemoryPayloadStore,
+    traits::{
+        CertifiedStream, EpochManager, EpochStream, EquivocationStream, Payload, PayloadStore,
+        PayloadValidator, SyncError, SyncSource,
+    },
+    types::{
+        Acknowledgment, BackpressurePolicy, Certificate, CertifiedEvent, ConductorError, Epoch,
+        EpochChange, Equivocation, Height, QueueStatus, RetentionPolicy, Snapshot, TransferError,
+    },
 };
 
 /// Configuration for the conductor.
@@ -22,11 +60,57 @@ pub struct ConductorConfig {
     ///
     /// Typically `2f + 1` where `f` is the Byzantine fault tolerance.
     pub quorum_threshold: usize,
+    /// Maximum number of committed payloads awaiting certification at once.
+    ///
+    /// Allows the sequencer to pipeline proposals instead of waiting a full
+    /// quorum round trip per payload.
+    pub max_pending: usize,
+    /// What [`Conductor::commit`] does when `max_pending` payloads are
+    /// already awaiting certification.
+    pub backpressure: BackpressurePolicy,
+    /// Number of certified payloads buffered for each
+    /// [`Conductor::subscribe_certified`] subscriber.
+    pub certified_capacity: usize,
+    /// How much certified history is kept in the store.
+    ///
+    /// Pair a bounded policy with a [`TieredPayloadStore`] to archive
+    /// evicted payloads instead of dropping them.
+    ///
+    /// [`TieredPayloadStore`]: crate::TieredPayloadStore
+    pub retention: RetentionPolicy,
+    /// Number of heights requested at a time by [`Conductor::sync`].
+    pub sync_batch_size: u64,
+    /// Whether [`Conductor::sync`] accepts payloads served without a
+    /// certificate.
+    ///
+    /// Payloads recorded through [`Conductor::certify`] have no
+    /// [`Certificate`], so nodes catching up on such a chain must trust
+    /// their sync source.
+    pub sync_trust_uncertified: bool,
+    /// How long a [`CommitReceipt`] returned by [`Conductor::commit`] waits
+    /// for certification before failing with
+    /// [`ConductorError::CommitTimeout`].
+    pub commit_timeout: Duration,
 }
 
+/// Default number of heights requested at a time while syncing.
+const DEFAULT_SYNC_BATCH_SIZE: u64 = 64;
+
+/// Default time a commit waits for certification.
+const DEFAULT_COMMIT_TIMEOUT: Duration = Duration::from_secs(30);
+
 impl Default for ConductorConfig {
     fn default() -> Self {
-        Self { quorum_threshold: 1 }
+        Self {
+            quorum_threshold: 1,
+            max_pending: DEFAULT_MAX_PENDING,
+            backpressure: BackpressurePolicy::Reject,
+            certified_capacity: DEFAULT_CERTIFIED_CAPACITY,
+            retention: RetentionPolicy::KeepAll,
+            sync_batch_size: DEFAULT_SYNC_BATCH_SIZE,
+            sync_trust_uncertified: false,
+            commit_timeout: DEFAULT_COMMIT_TIMEOUT,
+        }
     }
 }
 
@@ -39,6 +123,118 @@ struct ConductorState {
     current_epoch: u64,
     /// Whether we are currently the sequencer.
     is_sequencer: bool,
+    /// Background task applying epoch changes while running.
+    epoch_task: Option<EpochTask>,
+}
+
+/// Handle to the background task that applies epoch changes.
+struct EpochTask {
+    /// Signals the task to stop.
+    shutdown: oneshot::Sender<()>,
+    /// The running task.
+    handle: JoinHandle<()>,
+}
+
+/// Applies an epoch change to the conductor's state.
+///
+/// Leadership is rechecked by comparing `me` with the epoch manager's
+/// sequencer for the new epoch; the change's `is_self` flag is ignored. A
+/// proposal request the engine parked under the previous epoch is cancelled.
+async fn apply_epoch_change<P, E, St>(
+    state: &RwLock<ConductorState>,
+    automaton: &PayloadAutomaton<P, E::PublicKey, St>,
+    monitor: &EpochMonitor,
+    epoch_manager: &E,
+    me: &E::PublicKey,
+    change: &EpochChange<E::PublicKey>,
+) where
+    P: Payload,
+    E: EpochManager<PublicKey: PublicKey>,
+    St: PayloadStore<P>,
+{
+    let is_sequencer = is_sequencer_in(epoch_manager, me, change.epoch);
+    if change.is_self != is_sequencer {
+        debug!(
+            epoch = change.epoch,
+            is_self = change.is_self,
+            "ignoring epoch change leadership flag"
+        );
+    }
+
+    let mut state = state.write().await;
+    automaton.fence(change.epoch, is_sequencer).await;
+    state.current_epoch = change.epoch;
+    state.is_sequencer = is_sequencer;
+    monitor.update(change.epoch);
+    #[cfg(feature = "metrics")]
+    automaton.metrics().epoch_changed(change.epoch, is_sequencer);
+
+    info!(epoch = change.epoch, is_sequencer, "epoch changed");
+}
+
+/// Marks a [`Conductor::sync`] as in progress until dropped.
+///
+/// Overlapping syncs each hold a guard, so commits stay rejected until the
+/// last one finishes or is cancelled.
+struct SyncGuard(Arc<AtomicUsize>);
+
+impl SyncGuard {
+    fn new(syncing: &Arc<AtomicUsize>) -> Self {
+        syncing.fetch_add(1, Ordering::AcqRel);
+        Self(Arc::clone(syncing))
+    }
+}
+
+impl Drop for SyncGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Checks a [`Certificate`] proven by an engine
+/// [`ChunkProof`](crate::ChunkProof) against the engine's signing scheme.
+type ChunkProofVerifier<K, D> = Arc<dyn Fn(&Certificate<K, D>) -> bool + Send + Sync>;
+
+/// The [`ChunkProofVerifier`] shared by a conductor's clones, set by
+/// [`Conductor::spawn_engine`].
+type SharedChunkProofVerifier<K, D> = Arc<Mutex<Option<ChunkProofVerifier<K, D>>>>;
+
+/// Returns whether the chunk proof of `certificate` holds a quorum
+/// certificate from the engine's validators for the certificate's epoch
+/// over a chunk carrying its digest.
+fn verify_chunk_proof<K, D, V>(
+    validators: &V,
+    rng: &mut impl CryptoRngCore,
+    certificate: &Certificate<K, D>,
+) -> bool
+where
+    K: PublicKey,
+    D: commonware_cryptography::Digest,
+    V: certificate::Provider<Scope = ConsensusEpoch, Scheme: Scheme<K, D>>,
+{
+    let Some(proof) = &certificate.chunk else {
+        return false;
+    };
+    let epoch = ConsensusEpoch::new(certificate.epoch);
+    let Some(scheme) = validators.scoped(epoch) else {
+        return false;
+    };
+    let Ok(quorum) = <V::Scheme as certificate::Scheme>::Certificate::decode_cfg(
+        proof.certificate.as_slice(),
+        &scheme.certificate_codec_config(),
+    ) else {
+        return false;
+    };
+    let chunk =
+        Chunk::new(proof.sequencer.clone(), ConsensusHeight::new(proof.height), certificate.digest);
+    Lock::<K, V::Scheme, D>::new(chunk, epoch, quorum).verify(rng, &scheme, &Sequential)
+}
+
+/// Returns whether `me` is the epoch manager's sequencer for `epoch`.
+///
+/// An epoch without a known sequencer has no local leader.
+fn is_sequencer_in<E: EpochManager>(epoch_manager: &E, me: &E::PublicKey, epoch: Epoch) -> bool {
+    epoch_manager.sequencer(epoch).is_some_and(|sequencer| sequencer == *me)
 }
 
 /// Generic conductor over Payload, EpochManager, and Crypto scheme.
@@ -51,8 +247,11 @@ struct ConductorState {
 /// # Type Parameters
 ///
 /// * `P` - The payload type, must implement [`Payload`]
-/// * `E` - The epoch manager, must implement [`EpochManager`]
+/// * `E` - The epoch manager, must implement [`EpochManager`] over the
+///   signer's public key type
 /// * `S` - The cryptographic signer, must implement [`Signer`]
+/// * `St` - The store certified payloads are persisted to, must implement
+///   [`PayloadStore`]. Defaults to [`InMemoryPayloadStore`].
 ///
 /// # Example
 ///
@@ -64,37 +263,48 @@ struct ConductorState {
 ///
 /// // Check if we're the leader
 /// if conductor.leader().await {
-///     conductor.commit(payload).await?;
+///     let committed = conductor.commit(payload).await?.await?;
 /// }
 ///
 /// // Get the latest certified payload
 /// let latest = conductor.latest().await;
 /// ```
-pub struct Conductor<P, E, S>
+pub struct Conductor<P, E, S, St = InMemoryPayloadStore<P>>
 where
     P: Payload,
-    E: EpochManager,
+    E: EpochManager<PublicKey = S::PublicKey>,
     S: Signer,
+    St: PayloadStore<P>,
 {
     /// Configuration.
     config: ConductorConfig,
     /// The payload automaton.
-    automaton: PayloadAutomaton<P, E::PublicKey>,
+    automaton: PayloadAutomaton<P, E::PublicKey, St>,
     /// The epoch manager.
     epoch_manager: E,
     /// Our signer.
     signer: S,
+    /// Epoch monitor shared with a spawned engine.
+    monitor: EpochMonitor,
     /// Internal state.
     state: Arc<RwLock<ConductorState>>,
+    /// Number of [`Conductor::sync`] calls in progress.
+    syncing: Arc<AtomicUsize>,
+    /// Verifies engine chunk proofs, once [`Conductor::spawn_engine`] ran.
+    chunk_proofs: SharedChunkProofVerifier<E::PublicKey, P::Digest>,
+    /// Tasks started by [`Conductor::spawn_engine`], aborted on
+    /// [`Conductor::stop`].
+    engine_tasks: Arc<Mutex<Vec<Handle<()>>>>,
     /// Marker for the signer's public key type.
     _crypto: PhantomData<S>,
 }
 
-impl<P, E, S> Clone for Conductor<P, E, S>
+impl<P, E, S, St> Clone for Conductor<P, E, S, St>
 where
     P: Payload,
-    E: EpochManager,
+    E: EpochManager<PublicKey = S::PublicKey>,
     S: Signer + Clone,
+    St: PayloadStore<P>,
 {
     fn clone(&self) -> Self {
         Self {
@@ -102,17 +312,22 @@ where
             automaton: self.automaton.clone(),
             epoch_manager: self.epoch_manager.clone(),
             signer: self.signer.clone(),
+            monitor: self.monitor.clone(),
             state: Arc::clone(&self.state),
+            syncing: Arc::clone(&self.syncing),
+            chunk_proofs: Arc::clone(&self.chunk_proofs),
+            engine_tasks: Arc::clone(&self.engine_tasks),
             _crypto: PhantomData,
         }
     }
 }
 
-impl<P, E, S> std::fmt::Debug for Conductor<P, E, S>
+impl<P, E, S, St> std::fmt::Debug for Conductor<P, E, S, St>
 where
     P: Payload,
-    E: EpochManager,
+    E: EpochManager<PublicKey = S::PublicKey>,
     S: Signer,
+    St: PayloadStore<P>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Conductor").field("config", &self.config).finish_non_exhaustive()
@@ -122,29 +337,67 @@ where
 impl<P, E, S> Conductor<P, E, S>
 where
     P: Payload,
-    E: EpochManager,
+    E: EpochManager<PublicKey = S::PublicKey>,
     S: Signer,
 {
-    /// Creates a new conductor.
+    /// Creates a new conductor backed by an in-memory store.
     pub fn new(config: ConductorConfig, epoch_manager: E, signer: S) -> Self {
-        Self {
-            config,
-            automaton: PayloadAutomaton::new(),
-            epoch_manager,
-            signer,
-            state: Arc::new(RwLock::new(ConductorState::default())),
-            _crypto: PhantomData,
-        }
+        Self::with_automaton(config, PayloadAutomaton::new(), epoch_manager, signer)
     }
 
     /// Creates a new conductor initialized with a genesis payload.
     pub fn with_genesis(config: ConductorConfig, epoch_manager: E, signer: S, genesis: P) -> Self {
+        Self::with_automaton(config, PayloadAutomaton::with_genesis(genesis), epoch_manager, signer)
+    }
+}
+
+impl<P, E, S, St> Conductor<P, E, S, St>
+where
+    P: Payload,
+    E: EpochManager<PublicKey = S::PublicKey>,
+    S: Signer,
+    St: PayloadStore<P>,
+{
+    /// Creates a new conductor that persists certified payloads to `store`.
+    ///
+    /// The conductor resumes from the latest payload already in the store.
+    pub fn with_store(config: ConductorConfig, epoch_manager: E, signer: S, store: St) -> Self {
+        Self::with_automaton(config, PayloadAutomaton::with_store(store), epoch_manager, signer)
+    }
+
+    /// Sets the application-level validator run on committed payloads and
+    /// on payloads verified for the ordered_broadcast engine.
+    ///
+    /// Rejections surface as [`ConductorError::ValidationFailed`] with the
+    /// validator's reason. Combine several with a [`ValidatorChain`].
+    ///
+    /// [`ValidatorChain`]: crate::ValidatorChain
+    pub fn with_validator(mut self, validator: impl PayloadValidator<P>) -> Self {
+        self.automaton = self.automaton.with_validator(validator);
+        self
+    }
+
+    fn with_automaton(
+        config: ConductorConfig,
+        automaton: PayloadAutomaton<P, E::PublicKey, St>,
+        epoch_manager: E,
+        signer: S,
+    ) -> Self {
+        let automaton = automaton
+            .with_max_pending(config.max_pending)
+            .with_certified_capacity(config.certified_capacity)
+            .with_retention(config.retention);
+        let monitor = EpochMonitor::new(epoch_manager.current_epoch());
         Self {
             config,
-            automaton: PayloadAutomaton::with_genesis(genesis),
+            automaton,
             epoch_manager,
             signer,
+            monitor,
             state: Arc::new(RwLock::new(ConductorState::default())),
+            syncing: Arc::new(AtomicUsize::new(0)),
+            chunk_proofs: Arc::new(Mutex::new(None)),
+            engine_tasks: Arc::new(Mutex::new(Vec::new())),
             _crypto: PhantomData,
         }
     }
@@ -161,44 +414,123 @@ where
         self.epoch_manager.subscribe()
     }
 
+    /// Returns a stream of payloads certified from now on.
+    ///
+    /// Payloads arrive in height order along with the epoch they were
+    /// certified in. A subscriber that falls more than
+    /// [`ConductorConfig::certified_capacity`] payloads behind receives a
+    /// [`CertifiedEvent::Lagged`] with the number it missed, then resumes
+    /// with the oldest payload still buffered.
+    pub fn subscribe_certified(&self) -> CertifiedStream<P> {
+        let receiver = self.automaton.subscribe_certified();
+        Box::pin(stream::unfold(receiver, |mut receiver| async move {
+            let event = match receiver.recv().await {
+                Ok(certified) => CertifiedEvent::Certified(certified),
+                Err(RecvError::Lagged(missed)) => CertifiedEvent::Lagged { missed },
+                Err(RecvError::Closed) => return None,
+            };
+            Some((event, receiver))
+        }))
+    }
+
+    /// Returns a stream of equivocations detected from now on.
+    ///
+    /// Each [`Equivocation`] holds either two acknowledgments a validator
+    /// signed for different digests, or the digests of two payloads a
+    /// sequencer relayed, at the same epoch and height. A subscriber
+    /// that falls behind skips the equivocations it missed; the most recent
+    /// ones remain available from [`Conductor::equivocations`].
+    pub fn subscribe_equivocations(&self) -> EquivocationStream<S::PublicKey, P::Digest> {
+        let receiver = self.automaton.subscribe_equivocations();
+        Box::pin(stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(equivocation) => return Some((equivocation, receiver)),
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!(missed, "equivocation subscriber lagged");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }))
+    }
+
+    /// Returns the most recently detected equivocations, oldest first.
+    pub async fn equivocations(&self) -> Vec<Equivocation<S::PublicKey, P::Digest>> {
+        self.automaton.equivocations().await
+    }
+
     /// Commits a payload.
     ///
     /// This is the primary method for proposing new payloads. It will:
     /// 1. Verify the caller is the current sequencer
-    /// 2. Validate the payload
+    /// 2. Validate the payload against the newest in-flight payload
     /// 3. Submit it for certification
     ///
+    /// Returns once the payload is submitted, with a [`CommitReceipt`] that
+    /// resolves when it is certified. The receipt fails after
+    /// [`ConductorConfig::commit_timeout`], if an epoch change drops the
+    /// payload, or if a different payload is certified at its height.
+    ///
+    /// Committing a payload that is already pending or certified, e.g. when
+    /// retrying after a timeout, succeeds with a receipt for it; see
+    /// [`PayloadAutomaton::existing_receipt`].
+    ///
+    /// Up to [`ConductorConfig::max_pending`] payloads may be committed
+    /// before the first of them is certified. Beyond that, the commit fails
+    /// with [`ConductorError::Backpressure`], after waiting for room if
+    /// [`ConductorConfig::backpressure`] says so.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - The caller is not the current sequencer
+    /// - The node is catching up with [`Conductor::sync`]
+    /// - A different payload is already pending or certified at its height
     /// - The payload fails validation
-    /// - The internal channel is closed
-    pub async fn commit(&self, payload: P) -> Result<(), ConductorError> {
+    /// - The epoch changed while the payload was being submitted
+    /// - The proposal queue is full
+    pub async fn commit(&self, payload: P) -> Result<CommitReceipt<P::Digest>, ConductorError> {
+        let result = self.submit(payload).await;
+        #[cfg(feature = "metrics")]
+        self.automaton.metrics().commit(&result);
+        result
+    }
+
+    /// Submits a payload for [`Conductor::commit`].
+    async fn submit(&self, payload: P) -> Result<CommitReceipt<P::Digest>, ConductorError> {
         // Check if we're the sequencer
-        if !self.state.read().await.is_sequencer {
+        let state = self.state.read().await;
+        if self.syncing.load(Ordering::Acquire) > 0 {
+            return Err(ConductorError::Syncing);
+        }
+        if !state.is_sequencer {
             warn!("commit called but not sequencer");
             return Err(ConductorError::NotSequencer);
         }
+        // Tag the payload with the epoch we lead; if another sequencer takes
+        // over while we wait below, the automaton's fence rejects it
+        let epoch = state.current_epoch;
+        drop(state);
+
+        // A retry of a payload that already went through resolves with it
+        if let Some(receipt) = self.automaton.existing_receipt(&payload).await? {
+            debug!(height = payload.height(), "payload already committed");
+            return Ok(receipt.with_timeout(self.config.commit_timeout));
+        }
 
-        // Validate the payload
-        if !self.automaton.validate(&payload).await {
-            let expected_height = self.automaton.next_height().await;
-            let got_height = payload.height();
-
-            if expected_height != got_height {
-                return Err(ConductorError::InvalidHeight {
-                    expected: expected_height,
-                    got: got_height,
-                });
-            }
+        self.reserve(1).await?;
 
-            // Must be a parent mismatch
-            return Err(ConductorError::ValidationFailed("parent digest mismatch".to_string()));
+        // Waiting for room may have outlasted our epoch
+        let current = self.automaton.fenced_epoch().await;
+        if epoch < current {
+            return Err(ConductorError::StaleEpoch { epoch, current });
         }
 
+        // Validate the payload
+        self.automaton.validate(&payload).await?;
+
         // Get the quorum threshold
-        let epoch = self.state.read().await.current_epoch;
         let threshold =
             self.epoch_manager.quorum_threshold(epoch).unwrap_or(self.config.quorum_threshold);
 
@@ -208,14 +540,110 @@ where
             "submitting payload for certification"
         );
 
-        // Submit for certification
-        let rx = self.automaton.submit_proposal(payload, threshold).await;
+        // Submit for certification; another commit may have taken the room
+        let receipt = match self.automaton.submit_proposal(payload, epoch, threshold).await {
+            Err(ConductorError::WindowFull { .. }) => return Err(self.backpressure().await),
+            result => result?,
+        };
+        debug!(digest = ?receipt.digest(), "payload proposal accepted");
+        Ok(receipt.with_timeout(self.config.commit_timeout))
+    }
+
+    /// Commits a batch of consecutive payloads as one unit.
+    ///
+    /// The batch must form a parent-linked chain extending the newest
+    /// in-flight payload, or [`Conductor::latest`] if none is in flight. It
+    /// is submitted as a whole or not at all, and certified by
+    /// [`Conductor::acknowledge`] only once every payload in it reached
+    /// quorum, so a failure cannot leave a hole in the chain; an epoch
+    /// change before then drops the whole batch. The ordered_broadcast
+    /// engine started by [`Conductor::spawn_engine`] proposes each payload
+    /// as its own chunk, and the batch is certified once the engine
+    /// certified every one of them.
+    ///
+    /// Returns a [`CommitReceipt`] per payload, in height order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The caller is not the current sequencer
+    /// - The node is catching up with [`Conductor::sync`]
+    /// - The batch is empty, or a payload does not extend its predecessor
+    /// - The epoch changed while the batch was being submitted
+    /// - The batch is larger than [`ConductorConfig::max_pending`], with
+    ///   [`ConductorError::WindowFull`]; it can never fit, so split it
+    ///   rather than retry
+    /// - The proposal queue has no room for the batch, as for
+    ///   [`Conductor::commit`]
+    pub async fn commit_batch(
+        &self,
+        payloads: Vec<P>,
+    ) -> Result<Vec<CommitReceipt<P::Digest>>, ConductorError> {
+        let result = self.submit_batch(payloads).await;
+        #[cfg(feature = "metrics")]
+        self.automaton.metrics().commit(&result);
+        result
+    }
+
+    /// Submits a batch for [`Conductor::commit_batch`].
+    async fn submit_batch(
+        &self,
+        payloads: Vec<P>,
+    ) -> Result<Vec<CommitReceipt<P::Digest>>, ConductorError> {
+        let state = self.state.read().await;
+        if self.syncing.load(Ordering::Acquire) > 0 {
+            return Err(ConductorError::Syncing);
+        }
+        if !state.is_sequencer {
+            warn!("commit_batch called but not sequencer");
+            return Err(ConductorError::NotSequencer);
+        }
+        let epoch = state.current_epoch;
+        drop(state);
+
+        let threshold =
+            self.epoch_manager.quorum_threshold(epoch).unwrap_or(self.config.quorum_threshold);
+        let count = payloads.len();
+        self.reserve(count).await?;
+
+        debug!(payloads = count, threshold, "submitting batch for certification");
+        let receipts = match self.automaton.submit_batch(payloads, epoch, threshold).await {
+            Err(ConductorError::WindowFull { .. }) if count <= self.config.max_pending => {
+                return Err(self.backpressure().await);
+            }
+            result => result?,
+        };
+        Ok(receipts
+            .into_iter()
+            .map(|receipt| receipt.with_timeout(self.config.commit_timeout))
+            .collect())
+    }
+
+    /// Waits until `count` more payloads fit in the proposal queue, as
+    /// allowed by [`ConductorConfig::backpressure`].
+    ///
+    /// Returns right away if `count` can never fit, leaving the automaton to
+    /// reject the submission.
+    async fn reserve(&self, count: usize) -> Result<(), ConductorError> {
+        if count > self.config.max_pending {
+            return Ok(());
+        }
+        let room = match self.config.backpressure {
+            BackpressurePolicy::Reject => {
+                self.automaton.pending_count().await + count <= self.config.max_pending
+            }
+            BackpressurePolicy::Wait(timeout) => {
+                tokio::time::timeout(timeout, self.automaton.wait_for_room(count)).await.is_ok()
+            }
+        };
+        if room { Ok(()) } else { Err(self.backpressure().await) }
+    }
 
-        // Wait for the digest (proposal accepted)
-        rx.await.map_or(Err(ConductorError::ChannelClosed), |digest| {
-            debug!(?digest, "payload proposal accepted");
-            Ok(())
-        })
+    /// Builds the error returned when the proposal queue is full.
+    async fn backpressure(&self) -> ConductorError {
+        let depth = self.automaton.pending_count().await;
+        warn!(depth, "proposal queue full");
+        ConductorError::Backpressure { depth }
     }
 
     /// Returns the latest certified payload.
@@ -223,16 +651,202 @@ where
         self.automaton.latest().await
     }
 
-    /// Returns a certified payload by height.
-    pub async fn get_by_height(&self, height: u64) -> Option<P> {
+    /// Returns a certified payload by height, or `None` if no payload was
+    /// certified at `height`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConductorError::Pruned`] if the payload was pruned by the
+    /// configured [`RetentionPolicy`].
+    pub async fn get_by_height(&self, height: u64) -> Result<Option<P>, ConductorError> {
         self.automaton.get_by_height(height).await
     }
 
+    /// Catches up on certified payloads from `source`.
+    ///
+    /// Fetches every height between the latest local certified payload and
+    /// the source's tip, [`ConductorConfig::sync_batch_size`] heights at a
+    /// time. Each payload must extend the local chain and carry a
+    /// certificate that verifies against the epoch manager's validator set
+    /// and quorum threshold for the certificate's epoch, which must not be
+    /// after the current one; payloads are applied in height order as they
+    /// verify. Payloads certified by the ordered_broadcast engine verify
+    /// against its signing scheme, so only once [`Conductor::spawn_engine`]
+    /// ran. Commits are rejected with [`ConductorError::Syncing`] until
+    /// every sync in progress returns. Returns the number of payloads
+    /// applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConductorError::Sync`] if the source fails or serves a
+    /// payload that does not verify, or [`ConductorError::Store`] if a
+    /// payload could not be stored. Payloads applied before the failure are
+    /// kept.
+    pub async fn sync<Src>(&self, source: &Src) -> Result<u64, ConductorError>
+    where
+        Src: SyncSource<P, E::PublicKey>,
+    {
+        let _syncing = SyncGuard::new(&self.syncing);
+        self.catch_up(source).await
+    }
+
+    /// Applies the certified payloads `source` has beyond the local tip.
+    async fn catch_up<Src>(&self, source: &Src) -> Result<u64, ConductorError>
+    where
+        Src: SyncSource<P, E::PublicKey>,
+    {
+        let Some(tip) = source.tip().await? else {
+            return Ok(0);
+        };
+        let start = self.automaton.latest().await.map_or(0, |latest| latest.height() + 1);
+        if start > tip {
+            return Ok(0);
+        }
+        info!(from = start, tip, "catching up on certified payloads");
+
+        let batch = self.config.sync_batch_size.max(1);
+        let mut next = start;
+        while next <= tip {
+            let to = tip.min(next.saturating_add(batch - 1));
+            let entries = source.fetch(next, to).await?;
+            if entries.is_empty() {
+                return Err(SyncError::Incomplete { height: next }.into());
+            }
+            for (payload, certificate) in entries {
+                if payload.height() != next {
+                    return Err(SyncError::Incomplete { height: next }.into());
+                }
+                let epoch = self.verify_synced(&payload, certificate.as_ref()).await?;
+                self.automaton.apply_synced(payload, certificate, epoch).await?;
+                next += 1;
+            }
+        }
+
+        info!(tip, applied = next - start, "caught up");
+        Ok(next - start)
+    }
+
+    /// Checks the certificate of a synced payload, returning the epoch the
+    /// payload was certified in.
+    async fn verify_synced(
+        &self,
+        payload: &P,
+        certificate: Option<&Certificate<E::PublicKey, P::Digest>>,
+    ) -> Result<Epoch, SyncError> {
+        let height = payload.height();
+        let Some(certificate) = certificate else {
+            if self.config.sync_trust_uncertified {
+                return Ok(self.state.read().await.current_epoch);
+            }
+            return Err(SyncError::MissingCertificate { height });
+        };
+
+        // Verify against the validator set of the epoch the certificate
+        // claims. Nothing signed for an epoch not reached yet is trusted,
+        // whatever the epoch manager reports for it.
+        let valid = certificate.height == height
+            && certificate.digest == payload.digest()
+            && certificate.epoch <= self.epoch_manager.current_epoch()
+            && self.verify_certificate(certificate);
+        if !valid {
+            warn!(height, epoch = certificate.epoch, "synced certificate does not verify");
+            return Err(SyncError::InvalidCertificate { height });
+        }
+        Ok(certificate.epoch)
+    }
+
+    /// Returns whether `certificate` proves its payload was certified.
+    ///
+    /// Acknowledgment signatures must reach the epoch's quorum threshold
+    /// from its validator set; a chunk proof must verify against the signing
+    /// scheme of the engine started by [`Conductor::spawn_engine`].
+    fn verify_certificate(&self, certificate: &Certificate<E::PublicKey, P::Digest>) -> bool {
+        if certificate.chunk.is_some() {
+            let verifier = self.chunk_proofs.lock().expect("chunk proof lock poisoned").clone();
+            return verifier.is_some_and(|verify| verify(certificate));
+        }
+        let Some(threshold) = self.epoch_manager.quorum_threshold(certificate.epoch) else {
+            return false;
+        };
+        self.epoch_manager
+            .validators(certificate.epoch)
+            .is_some_and(|validators| certificate.verify(&validators, threshold))
+    }
+
+    /// Captures the certified chain for bootstrapping another node.
+    ///
+    /// See [`PayloadAutomaton::snapshot`].
+    pub async fn snapshot(&self) -> Snapshot<P, E::PublicKey> {
+        self.automaton.snapshot().await
+    }
+
+    /// Bootstraps the conductor from a trusted `snapshot`.
+    ///
+    /// Committing resumes at the height after the snapshot's latest payload.
+    /// See [`PayloadAutomaton::restore`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConductorError::InvalidSnapshot`] if the snapshot cannot be
+    /// applied, or [`ConductorError::Store`] if it could not be stored.
+    pub async fn restore(&self, snapshot: Snapshot<P, E::PublicKey>) -> Result<(), ConductorError> {
+        self.automaton.restore(snapshot).await
+    }
+
+    /// Returns the certificate proving the payload at `height` was certified.
+    ///
+    /// Returns `None` if no payload is certified at `height`, or if it was
+    /// recorded through [`Conductor::certify`] without a proof.
+    pub async fn certificate(
+        &self,
+        height: Height,
+    ) -> Option<Certificate<S::PublicKey, P::Digest>> {
+        self.automaton.certificate(height).await
+    }
+
+    /// Returns the latest certified payload together with its certificate.
+    ///
+    /// Returns `None` if there is no certified payload or the latest one has
+    /// no certificate.
+    pub async fn latest_certified_with_proof(
+        &self,
+    ) -> Option<(P, Certificate<S::PublicKey, P::Digest>)> {
+        let payload = self.automaton.latest().await?;
+        let certificate = self.automaton.certificate(payload.height()).await?;
+        Some((payload, certificate))
+    }
+
     /// Returns the expected next height.
+    ///
+    /// Accounts for committed payloads that are not yet certified.
     pub async fn next_height(&self) -> u64 {
         self.automaton.next_height().await
     }
 
+    /// Returns the number of committed payloads awaiting certification.
+    pub async fn pending_count(&self) -> usize {
+        self.automaton.pending_count().await
+    }
+
+    /// Returns the depth and capacity of the proposal queue, and how long
+    /// its oldest payload has been awaiting certification.
+    ///
+    /// Block builders can throttle on this instead of waiting for
+    /// [`ConductorError::Backpressure`].
+    pub async fn queue_status(&self) -> QueueStatus {
+        self.automaton.queue_status().await
+    }
+
+    /// Returns the conductor's metrics, to register with a Prometheus
+    /// registry.
+    ///
+    /// Acknowledgments exchanged inside the ordered_broadcast engine are not
+    /// counted.
+    #[cfg(feature = "metrics")]
+    pub const fn metrics(&self) -> &crate::Metrics {
+        self.automaton.metrics()
+    }
+
     /// Requests a leadership transfer.
     ///
     /// Delegates to the epoch manager's transfer mechanism.
@@ -243,27 +857,85 @@ where
 
     /// Starts the conductor.
     ///
-    /// This initializes the conductor and begins processing epoch changes.
-    /// Should be called before any other operations.
+    /// This initializes the conductor from the epoch manager's current epoch
+    /// and spawns a background task that applies every change from
+    /// [`EpochManager::subscribe`] until [`Conductor::stop`] is called.
+    /// Should be called before any other operations. Starting a running
+    /// conductor has no effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a tokio runtime, which runs the epoch change
+    /// task.
     pub async fn start(&self) {
         let mut state = self.state.write().await;
+        if state.running {
+            return;
+        }
+
+        // Subscribe before reading the current epoch so no change is missed
+        let mut changes = self.epoch_manager.subscribe();
+        let (shutdown, mut stopped) = oneshot::channel();
+        let me = self.signer.public_key();
+        let task_state = Arc::clone(&self.state);
+        let automaton = self.automaton.clone();
+        let monitor = self.monitor.clone();
+        let epoch_manager = self.epoch_manager.clone();
+        let task_me = me.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut stopped => break,
+                    change = changes.next() => {
+                        let Some(change) = change else {
+                            debug!("epoch change stream ended");
+                            break;
+                        };
+                        apply_epoch_change(
+                            &task_state,
+                            &automaton,
+                            &monitor,
+                            &epoch_manager,
+                            &task_me,
+                            &change,
+                        )
+                        .await;
+                    }
+                }
+            }
+        });
+        state.epoch_task = Some(EpochTask { shutdown, handle });
+
         state.running = true;
         state.current_epoch = self.epoch_manager.current_epoch();
-
-        // Check if we're the sequencer for the current epoch
-        if let Some(sequencer) = self.epoch_manager.sequencer(state.current_epoch) {
-            // We need to compare with our identity
-            // This requires knowing our public key from the signer
-            state.is_sequencer = self.epoch_manager.is_sequencer(&sequencer);
-        }
+        state.is_sequencer = is_sequencer_in(&self.epoch_manager, &me, state.current_epoch);
+        self.automaton.fence(state.current_epoch, state.is_sequencer).await;
+        self.monitor.update(state.current_epoch);
+        #[cfg(feature = "metrics")]
+        self.automaton.metrics().epoch(state.current_epoch, state.is_sequencer);
 
         info!(epoch = state.current_epoch, is_sequencer = state.is_sequencer, "conductor started");
     }
 
     /// Stops the conductor.
+    ///
+    /// Waits for the epoch change task to shut down, and aborts any engine
+    /// started by [`Conductor::spawn_engine`].
     pub async fn stop(&self) {
-        let mut state = self.state.write().await;
-        state.running = false;
+        let engine = std::mem::take(&mut *self.engine_tasks.lock().expect("engine lock poisoned"));
+        for handle in engine {
+            handle.abort();
+        }
+
+        let task = self.state.write().await.epoch_task.take();
+        if let Some(EpochTask { shutdown, handle }) = task {
+            let _ = shutdown.send(());
+            if let Err(e) = handle.await {
+                warn!(error = %e, "epoch change task failed");
+            }
+        }
+
+        self.state.write().await.running = false;
         info!("conductor stopped");
     }
 
@@ -279,35 +951,108 @@ where
 
     /// Handles an epoch change notification.
     ///
-    /// Updates internal state when the epoch transitions.
+    /// Updates internal state when the epoch transitions. Leadership is
+    /// decided by comparing the signer's public key with
+    /// [`EpochManager::sequencer`] for the new epoch, never by the change's
+    /// `is_self` flag. The new epoch fences the old one: a proposal request
+    /// the engine parked under the previous epoch is cancelled, and payloads
+    /// still awaiting certification are dropped unless this node remains the
+    /// sequencer, in which case they must be acknowledged again in the new
+    /// epoch. A running conductor applies changes from
+    /// its epoch manager on its own; this is for changes delivered out of
+    /// band.
     pub async fn handle_epoch_change(&self, change: EpochChange<E::PublicKey>) {
-        let mut state = self.state.write().await;
-        state.current_epoch = change.epoch;
-        state.is_sequencer = change.is_self;
+        let me = self.signer.public_key();
+        apply_epoch_change(
+            &self.state,
+            &self.automaton,
+            &self.monitor,
+            &self.epoch_manager,
+            &me,
+            &change,
+        )
+        .await;
+    }
 
-        info!(epoch = change.epoch, is_sequencer = change.is_self, "epoch changed");
+    /// Signs an acknowledgment of `payload` for the current epoch.
+    ///
+    /// Validators send the result to the sequencer, which records it with
+    /// [`Conductor::acknowledge`].
+    pub async fn sign_ack(&self, payload: &P) -> Acknowledgment<S::PublicKey, P::Digest> {
+        let epoch = self.state.read().await.current_epoch;
+        Acknowledgment::sign(&self.signer, epoch, payload.height(), payload.digest())
     }
 
-    /// Records an acknowledgment for the current pending payload.
+    /// Records a validator's signed acknowledgment of a pending payload.
+    ///
+    /// Called when receiving an ack from a validator. Each validator counts
+    /// at most once towards a payload's quorum; repeated acknowledgments and
+    /// acknowledgments for payloads that are not pending are ignored. A
+    /// validator signing two digests at one epoch and height is reported
+    /// through [`Conductor::subscribe_equivocations`]. Returns
+    /// the payloads certified as a result, in height order.
+    ///
+    /// # Errors
     ///
-    /// Called when receiving an ack from a validator.
-    /// Returns the certified payload if quorum is reached.
-    pub async fn acknowledge(&self) -> Option<P> {
-        self.automaton.acknowledge().await
+    /// Returns an error if:
+    /// - The acknowledgment was signed in an epoch other than the current one
+    /// - The signer is not a validator in the acknowledgment's epoch
+    /// - The signature does not verify
+    /// - A certified payload could not be persisted
+    pub async fn acknowledge(
+        &self,
+        ack: Acknowledgment<S::PublicKey, P::Digest>,
+    ) -> Result<Vec<P>, ConductorError> {
+        let current = self.state.read().await.current_epoch;
+        if ack.epoch != current {
+            warn!(epoch = ack.epoch, current, validator = %ack.validator, "acknowledgment from another epoch");
+            return Err(ConductorError::StaleEpoch { epoch: ack.epoch, current });
+        }
+        let is_validator = self
+            .epoch_manager
+            .validators(ack.epoch)
+            .is_some_and(|validators| validators.contains(&ack.validator));
+        if !is_validator {
+            warn!(epoch = ack.epoch, validator = %ack.validator, "acknowledgment from unknown validator");
+            return Err(ConductorError::UnknownValidator { epoch: ack.epoch });
+        }
+        if !ack.verify() {
+            warn!(epoch = ack.epoch, validator = %ack.validator, "acknowledgment with invalid signature");
+            return Err(ConductorError::InvalidSignature);
+        }
+
+        Ok(self.automaton.acknowledge(ack).await?)
     }
 
     /// Certifies a payload directly.
     ///
     /// Used by validators to record payloads that have been certified
-    /// by the sequencer.
-    pub async fn certify(&self, payload: P) {
-        self.automaton.certify(payload).await;
+    /// by the sequencer. Payloads that arrive ahead of a missing height are
+    /// buffered until the gap is filled; see [`Conductor::missing_heights`].
+    /// Returns the payloads applied as a result, in height order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a different payload is already certified at the
+    /// payload's height, if it does not extend the latest certified payload,
+    /// or if it could not be persisted. See [`PayloadAutomaton::certify`].
+    pub async fn certify(&self, payload: P) -> Result<Vec<P>, ConductorError> {
+        let epoch = self.state.read().await.current_epoch;
+        self.automaton.certify(payload, epoch).await
+    }
+
+    /// Returns the ranges of heights missing below payloads certified out of
+    /// order, in height order.
+    ///
+    /// Catch up on them with [`Conductor::sync`].
+    pub async fn missing_heights(&self) -> Vec<RangeInclusive<Height>> {
+        self.automaton.missing_heights().await
     }
 
     /// Returns a reference to the automaton.
     ///
     /// Useful for integrating with commonware's Engine.
-    pub const fn automaton(&self) -> &PayloadAutomaton<P, E::PublicKey> {
+    pub const fn automaton(&self) -> &PayloadAutomaton<P, E::PublicKey, St> {
         &self.automaton
     }
 
@@ -320,89 +1065,205 @@ where
     pub const fn signer(&self) -> &S {
         &self.signer
     }
+
+    /// Returns the epoch monitor handed to spawned engines.
+    pub const fn monitor(&self) -> &EpochMonitor {
+        &self.monitor
+    }
+}
+
+impl<P, E, S, St> Conductor<P, E, S, St>
+where
+    P: Payload,
+    E: EpochManager<PublicKey = S::PublicKey>,
+    S: Signer + Clone,
+    S::PublicKey: PublicKey,
+    St: PayloadStore<P>,
+{
+    /// Spawns a commonware ordered_broadcast engine driven by this conductor.
+    ///
+    /// While the local node is the sequencer, committed payloads are proposed
+    /// as chunks over `chunk_network` and their contents are relayed over
+    /// `payload_network`. Validators sign chunks over `ack_network`, and
+    /// every node certifies a payload once the engine assembles a quorum
+    /// certificate over its chunk, so no [`Conductor::acknowledge`] or
+    /// [`Conductor::certify`] calls are needed.
+    ///
+    /// Payloads certified by the engine are proven by the engine's quorum
+    /// certificate over their chunk, which [`Conductor::certificate`]
+    /// returns as the [`Certificate`]'s [`ChunkProof`]. From then on,
+    /// [`Conductor::sync`] verifies such proofs against `validators`.
+    ///
+    /// The engine and its payload relay run on `context` until
+    /// [`Conductor::stop`] aborts them.
+    ///
+    /// [`ChunkProof`]: crate::ChunkProof
+    pub fn spawn_engine<R, V>(
+        &self,
+        context: R,
+        config: EngineConfig,
+        validators: V,
+        chunk_network: (
+            impl Sender<PublicKey = S::PublicKey>,
+            impl Receiver<PublicKey = S::PublicKey>,
+        ),
+        ack_network: (
+            impl Sender<PublicKey = S::PublicKey>,
+            impl Receiver<PublicKey = S::PublicKey>,
+        ),
+        payload_network: (
+            impl Sender<PublicKey = S::PublicKey>,
+            impl Receiver<PublicKey = S::PublicKey>,
+        ),
+    ) where
+        R: Clock + Spawner + CryptoRngCore + Storage + Metrics,
+        V: certificate::Provider<Scope = ConsensusEpoch, Scheme: Scheme<S::PublicKey, P::Digest>>,
+    {
+        let rng = Arc::new(Mutex::new(context.clone()));
+        let provider = validators.clone();
+        let verify: ChunkProofVerifier<_, _> = Arc::new(move |certificate| {
+            let mut rng = rng.lock().expect("chunk proof rng lock poisoned");
+            verify_chunk_proof(&provider, &mut *rng, certificate)
+        });
+        *self.chunk_proofs.lock().expect("chunk proof lock poisoned") = Some(verify);
+
+        let (payload_sender, payload_receiver) = payload_network;
+        let relay = PayloadRelay::new(self.automaton.clone(), payload_sender);
+        let receiver = relay.clone();
+        let relay_task = context
+            .with_label("relay")
+            .spawn(move |_| async move { receiver.run(payload_receiver).await });
+
+        let engine = ordered_broadcast::Engine::new(
+            context,
+            ordered_broadcast::Config {
+                sequencer_signer: Some(ChunkSigner::new(&config.namespace, self.signer.clone())),
+                chunk_verifier: ChunkVerifier::new(&config.namespace),
+                sequencers_provider: EpochSequencersProvider::new(self.epoch_manager.clone()),
+                validators_provider: validators,
+                automaton: self.automaton.clone(),
+                relay,
+                reporter: PayloadReporter::new(self.automaton.clone()),
+                monitor: self.monitor.clone(),
+                priority_proposals: config.priority_proposals,
+                priority_acks: config.priority_acks,
+                rebroadcast_timeout: config.rebroadcast_timeout,
+                epoch_bounds: (
+                    EpochDelta::new(config.epoch_bounds.0),
+                    EpochDelta::new(config.epoch_bounds.1),
+                ),
+                height_bound: HeightDelta::new(config.height_bound),
+                journal_name_prefix: config.journal_name_prefix,
+                journal_heights_per_section: config.journal_heights_per_section,
+                journal_replay_buffer: config.journal_replay_buffer,
+                journal_write_buffer: config.journal_write_buffer,
+                journal_compression: config.journal_compression,
+                journal_buffer_pool: PoolRef::new(
+                    config.journal_page_size,
+                    config.journal_page_cache_size,
+                ),
+                strategy: Sequential,
+            },
+        );
+        info!("spawning ordered_broadcast engine");
+        let engine_task = engine.start(chunk_network, ack_network);
+        self.engine_tasks.lock().expect("engine lock poisoned").extend([relay_task, engine_task]);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use commonware_cryptography::{Hasher as _, ed25519, sha256};
-    use futures::stream;
+    use std::collections::BTreeMap;
+
+    use commonware_codec::{Decode as _, Encode as _};
+    use commonware_consensus::{Automaton as _, types::Height as ConsensusHeight};
+    use commonware_cryptography::{Signer as _, ed25519, sha256};
+    use futures::{StreamExt as _, stream};
+    use tokio::sync::broadcast;
 
     use super::*;
-    use crate::types::Height;
+    use crate::{
+        PayloadContext, TieredPayloadStore, ValidatorChain,
+        test_utils::TestPayload,
+        types::{CertifiedPayload, Committed},
+    };
 
     // Test payload using commonware's sha256::Digest
-    #[derive(Clone, Debug, PartialEq)]
-    struct TestPayload {
-        data: Vec<u8>,
-        height: Height,
+    // Mock epoch manager
+    #[derive(Clone)]
+    struct MockEpochManager {
+        is_sequencer: bool,
+        changes: Option<broadcast::Sender<EpochChange<ed25519::PublicKey>>>,
     }
 
-    impl Payload for TestPayload {
-        type Digest = sha256::Digest;
+    impl EpochManager for MockEpochManager {
+        type PublicKey = ed25519::PublicKey;
+
+        fn current_epoch(&self) -> u64 {
+            0
+        }
 
-        fn digest(&self) -> Self::Digest {
-            let mut hasher = sha256::Sha256::new();
-            hasher.update(&self.height.to_le_bytes());
-            hasher.update(&self.data);
-            hasher.finalize()
+        // The conductor's signer leads even epochs if `is_sequencer`, and odd
+        // epochs otherwise
+        fn sequencer(&self, epoch: u64) -> Option<Self::PublicKey> {
+            if self.is_sequencer == (epoch % 2 == 0) {
+                Some(create_test_signer().public_key())
+            } else {
+                Some(validator(1).public_key())
+            }
         }
 
-        fn height(&self) -> Height {
-            self.height
+        async fn transfer_leader(&self) -> Result<(), TransferError> {
+            Err(TransferError::NotSupported)
         }
 
-        fn encode(&self) -> Vec<u8> {
-            let mut buf = Vec::new();
-            buf.extend_from_slice(&self.height.to_le_bytes());
-            buf.extend_from_slice(&self.data);
-            buf
+        fn subscribe(&self) -> EpochStream<Self::PublicKey> {
+            let Some(changes) = &self.changes else {
+                return Box::pin(stream::empty());
+            };
+            Box::pin(stream::unfold(changes.subscribe(), |mut changes| async move {
+                changes.recv().await.ok().map(|change| (change, changes))
+            }))
         }
 
-        fn decode(bytes: &[u8]) -> Option<Self> {
-            if bytes.len() < 8 {
-                return None;
-            }
-            let height = u64::from_le_bytes(bytes[..8].try_into().ok()?);
-            let data = bytes[8..].to_vec();
-            Some(Self { data, height })
+        fn validators(&self, _epoch: u64) -> Option<Vec<Self::PublicKey>> {
+            Some(vec![validator(1).public_key(), validator(2).public_key()])
+        }
+
+        fn quorum_threshold(&self, _epoch: u64) -> Option<usize> {
+            Some(2)
         }
     }
 
-    // Mock epoch manager
+    // Epoch manager that knows no quorum threshold
     #[derive(Clone)]
-    struct MockEpochManager {
-        is_sequencer: bool,
-    }
+    struct QuorumlessEpochManager(MockEpochManager);
 
-    impl EpochManager for MockEpochManager {
-        type PublicKey = String;
+    impl EpochManager for QuorumlessEpochManager {
+        type PublicKey = ed25519::PublicKey;
 
         fn current_epoch(&self) -> u64 {
-            0
+            self.0.current_epoch()
         }
 
-        fn sequencer(&self, _epoch: u64) -> Option<Self::PublicKey> {
-            Some("sequencer".to_string())
-        }
-
-        fn is_sequencer(&self, _key: &Self::PublicKey) -> bool {
-            self.is_sequencer
+        fn sequencer(&self, epoch: u64) -> Option<Self::PublicKey> {
+            self.0.sequencer(epoch)
         }
 
         async fn transfer_leader(&self) -> Result<(), TransferError> {
-            Err(TransferError::NotSupported)
+            self.0.transfer_leader().await
         }
 
         fn subscribe(&self) -> EpochStream<Self::PublicKey> {
-            Box::pin(stream::empty())
+            self.0.subscribe()
         }
 
-        fn validators(&self, _epoch: u64) -> Option<Vec<Self::PublicKey>> {
-            Some(vec!["validator1".to_string(), "validator2".to_string()])
+        fn validators(&self, epoch: u64) -> Option<Vec<Self::PublicKey>> {
+            self.0.validators(epoch)
         }
 
         fn quorum_threshold(&self, _epoch: u64) -> Option<usize> {
-            Some(2)
+            None
         }
     }
 
@@ -410,21 +1271,28 @@ mod tests {
     type MockSigner = ed25519::PrivateKey;
 
     fn create_test_signer() -> MockSigner {
-        use commonware_cryptography::Signer as _;
         MockSigner::from_seed(42)
     }
 
+    fn validator(seed: u64) -> MockSigner {
+        MockSigner::from_seed(seed)
+    }
+
+    fn ack(seed: u64, payload: &TestPayload) -> Acknowledgment<ed25519::PublicKey, sha256::Digest> {
+        Acknowledgment::sign(&validator(seed), 0, payload.height(), payload.digest())
+    }
+
     #[tokio::test]
     async fn test_conductor_not_sequencer() {
         let config = ConductorConfig::default();
-        let epoch_manager = MockEpochManager { is_sequencer: false };
+        let epoch_manager = MockEpochManager { is_sequencer: false, changes: None };
         let signer = create_test_signer();
 
         let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
             Conductor::new(config, epoch_manager, signer);
         conductor.start().await;
 
-        let payload = TestPayload { data: vec![1, 2, 3], height: 0 };
+        let payload = TestPayload { data: vec![1, 2, 3], height: 0, parent: None };
 
         let result = conductor.commit(payload).await;
         assert!(matches!(result, Err(ConductorError::NotSequencer)));
@@ -433,7 +1301,7 @@ mod tests {
     #[tokio::test]
     async fn test_conductor_commit_as_sequencer() {
         let config = ConductorConfig::default();
-        let epoch_manager = MockEpochManager { is_sequencer: true };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
         let signer = create_test_signer();
 
         let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
@@ -446,7 +1314,7 @@ mod tests {
             state.running = true;
         }
 
-        let payload = TestPayload { data: vec![1, 2, 3], height: 0 };
+        let payload = TestPayload { data: vec![1, 2, 3], height: 0, parent: None };
 
         let result = conductor.commit(payload).await;
         assert!(result.is_ok());
@@ -455,10 +1323,10 @@ mod tests {
     #[tokio::test]
     async fn test_conductor_invalid_height() {
         let config = ConductorConfig::default();
-        let epoch_manager = MockEpochManager { is_sequencer: true };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
         let signer = create_test_signer();
 
-        let genesis = TestPayload { data: vec![0], height: 0 };
+        let genesis = TestPayload { data: vec![0], height: 0, parent: None };
         let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
             Conductor::with_genesis(config, epoch_manager, signer, genesis);
 
@@ -473,16 +1341,64 @@ mod tests {
         let payload = TestPayload {
             data: vec![1, 2, 3],
             height: 5, // Should be 1
+            parent: None,
         };
 
         let result = conductor.commit(payload).await;
         assert!(matches!(result, Err(ConductorError::InvalidHeight { expected: 1, got: 5 })));
     }
 
+    #[tokio::test]
+    async fn test_conductor_runs_validator_chain() {
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let validator = ValidatorChain::new()
+            .with(|payload: &TestPayload, _: Option<&TestPayload>| {
+                if payload.data.len() > 2 {
+                    return Err("payload too large".to_string());
+                }
+                Ok(())
+            })
+            .with(|payload: &TestPayload, parent: Option<&TestPayload>| {
+                if parent.is_some_and(|parent| payload.data < parent.data) {
+                    return Err("data went backwards".to_string());
+                }
+                Ok(())
+            });
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(ConductorConfig::default(), epoch_manager, create_test_signer())
+                .with_validator(validator);
+        conductor.start().await;
+
+        let result =
+            conductor.commit(TestPayload { data: vec![1, 2, 3], height: 0, parent: None }).await;
+        assert_eq!(
+            result.err(),
+            Some(ConductorError::ValidationFailed("payload too large".to_string()))
+        );
+        conductor.commit(TestPayload { data: vec![5], height: 0, parent: None }).await.unwrap();
+
+        // Checked against the newest in-flight payload, including in batches
+        let result = conductor.commit(TestPayload { data: vec![4], height: 1, parent: None }).await;
+        assert_eq!(
+            result.err(),
+            Some(ConductorError::ValidationFailed("data went backwards".to_string()))
+        );
+        let batch = vec![
+            TestPayload { data: vec![6], height: 1, parent: None },
+            TestPayload { data: vec![1], height: 2, parent: None },
+        ];
+        let result = conductor.commit_batch(batch).await;
+        assert_eq!(
+            result.err(),
+            Some(ConductorError::ValidationFailed("data went backwards".to_string()))
+        );
+        assert_eq!(conductor.pending_count().await, 1);
+    }
+
     #[tokio::test]
     async fn test_conductor_epoch_change() {
         let config = ConductorConfig::default();
-        let epoch_manager = MockEpochManager { is_sequencer: false };
+        let epoch_manager = MockEpochManager { is_sequencer: false, changes: None };
         let signer = create_test_signer();
 
         let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
@@ -495,22 +1411,107 @@ mod tests {
         conductor
             .handle_epoch_change(EpochChange {
                 epoch: 1,
-                sequencer: "us".to_string(),
+                sequencer: create_test_signer().public_key(),
                 is_self: true,
             })
             .await;
 
         assert!(conductor.leader().await);
         assert_eq!(conductor.current_epoch().await, 1);
+
+        // Leadership follows the epoch manager, not the caller's flag
+        conductor
+            .handle_epoch_change(EpochChange {
+                epoch: 2,
+                sequencer: create_test_signer().public_key(),
+                is_self: true,
+            })
+            .await;
+        assert!(!conductor.leader().await);
+    }
+
+    #[tokio::test]
+    async fn test_conductor_leadership_uses_signer_identity() {
+        // Epoch 0 is led by seed 42, so only the conductor signing with that
+        // key leads
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(ConductorConfig::default(), epoch_manager.clone(), validator(1));
+        conductor.start().await;
+        assert!(!conductor.leader().await);
+
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(ConductorConfig::default(), epoch_manager, create_test_signer());
+        conductor.start().await;
+        assert!(conductor.leader().await);
+    }
+
+    #[tokio::test]
+    async fn test_conductor_drives_parked_proposals() {
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(ConductorConfig::default(), epoch_manager, create_test_signer());
+        conductor.start().await;
+
+        let ctx = PayloadContext {
+            sequencer: create_test_signer().public_key(),
+            height: ConsensusHeight::zero(),
+        };
+        let mut automaton = conductor.automaton().clone();
+
+        // A parked proposal is fulfilled by the next commit
+        let rx = automaton.propose(ctx.clone()).await;
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        conductor.commit(payload.clone()).await.unwrap();
+        assert_eq!(rx.await.unwrap(), payload.digest());
+        conductor.certify(payload).await.unwrap();
+
+        // and cancelled when the epoch changes
+        let rx = automaton.propose(ctx).await;
+        conductor
+            .handle_epoch_change(EpochChange {
+                epoch: 1,
+                sequencer: create_test_signer().public_key(),
+                is_self: true,
+            })
+            .await;
+        assert!(rx.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_conductor_applies_epoch_changes() {
+        let (changes, _) = broadcast::channel(8);
+        let epoch_manager =
+            MockEpochManager { is_sequencer: false, changes: Some(changes.clone()) };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(ConductorConfig::default(), epoch_manager, create_test_signer());
+        conductor.start().await;
+        assert!(!conductor.leader().await);
+
+        let sequencer = create_test_signer().public_key();
+        changes
+            .send(EpochChange { epoch: 1, sequencer: sequencer.clone(), is_self: true })
+            .unwrap();
+        while conductor.current_epoch().await != 1 {
+            tokio::task::yield_now().await;
+        }
+        assert!(conductor.leader().await);
+
+        // Changes are no longer applied once stopped
+        conductor.stop().await;
+        assert!(!conductor.is_running().await);
+        assert_eq!(changes.receiver_count(), 0);
+        assert!(changes.send(EpochChange { epoch: 2, sequencer, is_self: false }).is_err());
+        assert_eq!(conductor.current_epoch().await, 1);
     }
 
     #[tokio::test]
     async fn test_conductor_latest() {
         let config = ConductorConfig::default();
-        let epoch_manager = MockEpochManager { is_sequencer: true };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
         let signer = create_test_signer();
 
-        let genesis = TestPayload { data: vec![0], height: 0 };
+        let genesis = TestPayload { data: vec![0], height: 0, parent: None };
         let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
             Conductor::with_genesis(config, epoch_manager, signer, genesis.clone());
 
@@ -520,8 +1521,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_conductor_acknowledge() {
-        let config = ConductorConfig { quorum_threshold: 2 };
-        let epoch_manager = MockEpochManager { is_sequencer: true };
+        let config = ConductorConfig { quorum_threshold: 2, ..Default::default() };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
         let signer = create_test_signer();
 
         let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
@@ -534,24 +1535,343 @@ mod tests {
             state.running = true;
         }
 
-        let payload = TestPayload { data: vec![1, 2, 3], height: 0 };
+        let payload = TestPayload { data: vec![1, 2, 3], height: 0, parent: None };
 
         // Commit the payload
         conductor.commit(payload.clone()).await.unwrap();
 
         // First ack - not certified
-        assert!(conductor.acknowledge().await.is_none());
+        assert!(conductor.acknowledge(ack(1, &payload)).await.unwrap().is_empty());
+
+        // Repeated ack from the same validator does not count twice
+        assert!(conductor.acknowledge(ack(1, &payload)).await.unwrap().is_empty());
+
+        // Second distinct validator - certified
+        let certified = conductor.acknowledge(ack(2, &payload)).await.unwrap();
+        assert_eq!(certified, vec![payload.clone()]);
+
+        // The certificate verifies against the epoch's validator set
+        let (latest, certificate) = conductor.latest_certified_with_proof().await.unwrap();
+        assert_eq!(latest, payload);
+        assert_eq!(conductor.certificate(0).await, Some(certificate.clone()));
+        let validators = conductor.epoch_manager().validators(0).unwrap();
+        assert!(certificate.verify(&validators, 2));
+    }
+
+    #[tokio::test]
+    async fn test_conductor_rejects_invalid_acknowledgments() {
+        let config = ConductorConfig::default();
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let signer = create_test_signer();
+
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, signer);
+        conductor.state.write().await.is_sequencer = true;
+
+        let payload = TestPayload { data: vec![1, 2, 3], height: 0, parent: None };
+        conductor.commit(payload.clone()).await.unwrap();
+
+        // Not in the validator set
+        let result = conductor.acknowledge(ack(3, &payload)).await;
+        assert_eq!(result, Err(ConductorError::UnknownValidator { epoch: 0 }));
+
+        // Validator key with a signature over a different message
+        let mut forged = ack(1, &payload);
+        forged.signature = ack(2, &payload).signature;
+        let result = conductor.acknowledge(forged).await;
+        assert_eq!(result, Err(ConductorError::InvalidSignature));
+
+        assert_eq!(conductor.pending_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_conductor_fences_stale_epochs() {
+        let config = ConductorConfig { quorum_threshold: 2, ..Default::default() };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        conductor.start().await;
+
+        let payload = TestPayload { data: vec![1, 2, 3], height: 0, parent: None };
+        let receipt = conductor.commit(payload.clone()).await.unwrap();
+        assert!(conductor.acknowledge(ack(1, &payload)).await.unwrap().is_empty());
+
+        // Still leading epoch 2: the proposal is carried over, but acks from
+        // epoch 0 no longer count
+        let sequencer = create_test_signer().public_key();
+        conductor
+            .handle_epoch_change(EpochChange {
+                epoch: 2,
+                sequencer: sequencer.clone(),
+                is_self: true,
+            })
+            .await;
+        assert_eq!(conductor.pending_count().await, 1);
+        let result = conductor.acknowledge(ack(2, &payload)).await;
+        assert_eq!(result, Err(ConductorError::StaleEpoch { epoch: 0, current: 2 }));
+        let signed =
+            |seed| Acknowledgment::sign(&validator(seed), 2, payload.height(), payload.digest());
+        assert!(conductor.acknowledge(signed(1)).await.unwrap().is_empty());
+
+        // Losing leadership drops the proposal before it reaches quorum
+        conductor.handle_epoch_change(EpochChange { epoch: 3, sequencer, is_self: false }).await;
+        assert_eq!(conductor.pending_count().await, 0);
+        let result = conductor.acknowledge(signed(2)).await;
+        assert_eq!(result, Err(ConductorError::StaleEpoch { epoch: 2, current: 3 }));
+        assert_eq!(conductor.latest().await, None);
+        assert_eq!(receipt.await, Err(ConductorError::EpochChanged { height: 0, epoch: 3 }));
+    }
+
+    #[tokio::test]
+    async fn test_conductor_commit_receipt() {
+        let config = ConductorConfig {
+            quorum_threshold: 2,
+            commit_timeout: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        conductor.start().await;
+
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        let receipt = conductor.commit(payload.clone()).await.unwrap();
+        assert_eq!(receipt.height(), 0);
+        conductor.acknowledge(ack(1, &payload)).await.unwrap();
+        conductor.acknowledge(ack(2, &payload)).await.unwrap();
+        let committed = receipt.await.unwrap();
+        assert_eq!(committed, Committed { height: 0, digest: payload.digest(), epoch: 0 });
+
+        // Without a quorum the receipt times out, but the payload stays pending
+        let payload = TestPayload { data: vec![2], height: 1, parent: None };
+        let receipt = conductor.commit(payload).await.unwrap();
+        assert_eq!(receipt.await, Err(ConductorError::CommitTimeout { height: 1 }));
+        assert_eq!(conductor.pending_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_conductor_sign_ack() {
+        let config = ConductorConfig::default();
+        let epoch_manager = MockEpochManager { is_sequencer: false, changes: None };
+
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, validator(1));
 
-        // Second ack - certified
-        let certified = conductor.acknowledge().await;
-        assert!(certified.is_some());
-        assert_eq!(certified.unwrap(), payload);
+        let payload = TestPayload { data: vec![1, 2, 3], height: 0, parent: None };
+        let signed = conductor.sign_ack(&payload).await;
+        assert_eq!(signed.validator, validator(1).public_key());
+        assert_eq!(signed.digest, payload.digest());
+        assert!(signed.verify());
+    }
+
+    #[tokio::test]
+    async fn test_conductor_pipelined_commits() {
+        let config = ConductorConfig { quorum_threshold: 1, max_pending: 2, ..Default::default() };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let signer = create_test_signer();
+
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, signer);
+        conductor.state.write().await.is_sequencer = true;
+
+        // Two payloads may be in flight at once
+        conductor.commit(TestPayload { data: vec![0], height: 0, parent: None }).await.unwrap();
+        conductor.commit(TestPayload { data: vec![1], height: 1, parent: None }).await.unwrap();
+        assert_eq!(conductor.next_height().await, 2);
+
+        let result = conductor.commit(TestPayload { data: vec![2], height: 2, parent: None }).await;
+        assert_eq!(result.err(), Some(ConductorError::Backpressure { depth: 2 }));
+        assert_eq!(conductor.queue_status().await.depth, 2);
+
+        // Certifying the first (epoch quorum of 2) frees a slot in the window
+        let first = TestPayload { data: vec![0], height: 0, parent: None };
+        conductor.acknowledge(ack(1, &first)).await.unwrap();
+        conductor.acknowledge(ack(2, &first)).await.unwrap();
+        conductor.commit(TestPayload { data: vec![2], height: 2, parent: None }).await.unwrap();
+        assert_eq!(conductor.pending_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_conductor_backpressure_waits() {
+        let config = ConductorConfig {
+            quorum_threshold: 1,
+            max_pending: 1,
+            backpressure: BackpressurePolicy::Wait(Duration::from_millis(50)),
+            ..Default::default()
+        };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        conductor.state.write().await.is_sequencer = true;
+
+        let first = TestPayload { data: vec![0], height: 0, parent: None };
+        conductor.commit(first.clone()).await.unwrap();
+
+        // Nothing is certified within the wait
+        let result = conductor.commit(TestPayload { data: vec![1], height: 1, parent: None }).await;
+        assert_eq!(result.err(), Some(ConductorError::Backpressure { depth: 1 }));
+
+        // The commit goes through once the first payload is certified
+        let (result, _) = tokio::join!(
+            conductor.commit(TestPayload { data: vec![1], height: 1, parent: None }),
+            async {
+                tokio::task::yield_now().await;
+                conductor.acknowledge(ack(1, &first)).await.unwrap();
+                conductor.acknowledge(ack(2, &first)).await.unwrap();
+            }
+        );
+        assert_eq!(result.unwrap().height(), 1);
+        assert_eq!(conductor.pending_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_conductor_waiting_commit_keeps_its_epoch() {
+        let (changes, _) = broadcast::channel(8);
+        let config = ConductorConfig {
+            max_pending: 1,
+            backpressure: BackpressurePolicy::Wait(Duration::from_secs(10)),
+            ..Default::default()
+        };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: Some(changes.clone()) };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        conductor.start().await;
+        conductor.commit(TestPayload { data: vec![0], height: 0, parent: None }).await.unwrap();
+
+        // Another sequencer takes over while the commit waits for room, and
+        // dropping the stale proposals makes room for it
+        let (result, _) = tokio::join!(
+            conductor.commit(TestPayload { data: vec![1], height: 1, parent: None }),
+            async {
+                tokio::task::yield_now().await;
+                let sequencer = validator(1).public_key();
+                changes.send(EpochChange { epoch: 1, sequencer, is_self: false }).unwrap();
+                while conductor.current_epoch().await != 1 {
+                    tokio::task::yield_now().await;
+                }
+            }
+        );
+        assert_eq!(result.err(), Some(ConductorError::StaleEpoch { epoch: 0, current: 1 }));
+        assert_eq!(conductor.pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_conductor_commit_is_idempotent() {
+        let config = ConductorConfig { quorum_threshold: 2, ..Default::default() };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        conductor.start().await;
+
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        let first = conductor.commit(payload.clone()).await.unwrap();
+        let retry = conductor.commit(payload.clone()).await.unwrap();
+        assert_eq!(conductor.pending_count().await, 1);
+
+        // A different payload at the same height is still rejected
+        let result = conductor.commit(TestPayload { data: vec![2], height: 0, parent: None }).await;
+        assert_eq!(result.err(), Some(ConductorError::InvalidHeight { expected: 1, got: 0 }));
+
+        conductor.acknowledge(ack(1, &payload)).await.unwrap();
+        conductor.acknowledge(ack(2, &payload)).await.unwrap();
+        let committed = Committed { height: 0, digest: payload.digest(), epoch: 0 };
+        assert_eq!(first.await, Ok(committed));
+        assert_eq!(retry.await, Ok(committed));
+
+        // Retrying once certified resolves immediately
+        assert_eq!(conductor.commit(payload).await.unwrap().await, Ok(committed));
+        let result = conductor.commit(TestPayload { data: vec![2], height: 0, parent: None }).await;
+        assert_eq!(result.err(), Some(ConductorError::InvalidHeight { expected: 1, got: 0 }));
+    }
+
+    #[tokio::test]
+    async fn test_conductor_commit_batch() {
+        let config = ConductorConfig { quorum_threshold: 2, max_pending: 3, ..Default::default() };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        conductor.start().await;
+
+        let batch: Vec<_> =
+            (0..3).map(|height| TestPayload { data: vec![1], height, parent: None }).collect();
+        let result = conductor.commit_batch(batch[1..].to_vec()).await;
+        assert_eq!(result.err(), Some(ConductorError::InvalidHeight { expected: 0, got: 1 }));
+        let too_long =
+            (0..4).map(|height| TestPayload { data: vec![1], height, parent: None }).collect();
+        let result = conductor.commit_batch(too_long).await;
+        assert_eq!(result.err(), Some(ConductorError::WindowFull { limit: 3 }));
+        assert_eq!(conductor.pending_count().await, 0);
+
+        let receipts = conductor.commit_batch(batch.clone()).await.unwrap();
+        assert_eq!(conductor.next_height().await, 3);
+        for payload in &batch[..2] {
+            for seed in [1, 2] {
+                assert!(conductor.acknowledge(ack(seed, payload)).await.unwrap().is_empty());
+            }
+        }
+        conductor.acknowledge(ack(1, &batch[2])).await.unwrap();
+        assert_eq!(conductor.acknowledge(ack(2, &batch[2])).await.unwrap(), batch);
+
+        let heights: Vec<_> = futures::future::try_join_all(receipts)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|committed| committed.height)
+            .collect();
+        assert_eq!(heights, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_conductor_subscribe_certified() {
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(ConductorConfig::default(), epoch_manager, create_test_signer());
+        conductor.start().await;
+        let mut certified = conductor.subscribe_certified();
+
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: None };
+        conductor.commit(p0.clone()).await.unwrap();
+        conductor.commit(p1.clone()).await.unwrap();
+
+        // The second payload reaches quorum first but is delivered second
+        conductor.acknowledge(ack(1, &p1)).await.unwrap();
+        conductor.acknowledge(ack(2, &p1)).await.unwrap();
+        conductor.acknowledge(ack(1, &p0)).await.unwrap();
+        conductor.acknowledge(ack(2, &p0)).await.unwrap();
+
+        for payload in [p0, p1] {
+            let expected = CertifiedPayload { height: payload.height, payload, epoch: 0 };
+            assert_eq!(certified.next().await, Some(CertifiedEvent::Certified(expected)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conductor_subscribe_certified_lagged() {
+        let config = ConductorConfig { certified_capacity: 1, ..Default::default() };
+        let epoch_manager = MockEpochManager { is_sequencer: false, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        let mut certified = conductor.subscribe_certified();
+
+        for height in 0..3 {
+            conductor
+                .certify(TestPayload { data: vec![height as u8], height, parent: None })
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(certified.next().await, Some(CertifiedEvent::Lagged { missed: 2 }));
+        let Some(CertifiedEvent::Certified(latest)) = certified.next().await else {
+            panic!("expected a certified payload");
+        };
+        assert_eq!(latest.height, 2);
     }
 
     #[tokio::test]
     async fn test_conductor_transfer_leader() {
         let config = ConductorConfig::default();
-        let epoch_manager = MockEpochManager { is_sequencer: true };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
         let signer = create_test_signer();
 
         let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
@@ -560,4 +1880,246 @@ mod tests {
         let result = conductor.transfer_leader().await;
         assert!(matches!(result, Err(TransferError::NotSupported)));
     }
+
+    #[tokio::test]
+    async fn test_conductor_with_store() {
+        let config = ConductorConfig::default();
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let signer = create_test_signer();
+
+        let genesis = TestPayload { data: vec![0], height: 0, parent: None };
+        let store: InMemoryPayloadStore<TestPayload> = [genesis.clone()].into_iter().collect();
+        let conductor = Conductor::with_store(config, epoch_manager, signer, store.clone());
+
+        assert_eq!(conductor.latest().await, Some(genesis));
+        assert_eq!(conductor.next_height().await, 1);
+
+        // Payloads certified through the conductor land in the shared store
+        let payload = TestPayload { data: vec![1], height: 1, parent: None };
+        conductor.certify(payload.clone()).await.unwrap();
+        assert_eq!(store.get_by_height(1).await, Some(payload));
+
+        // Payloads certified without a proof have no certificate
+        assert!(conductor.latest_certified_with_proof().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_conductor_retention_archives_evicted_payloads() {
+        let config =
+            ConductorConfig { retention: RetentionPolicy::KeepLast(1), ..Default::default() };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let store =
+            TieredPayloadStore::new(InMemoryPayloadStore::new(), InMemoryPayloadStore::new());
+        let conductor =
+            Conductor::with_store(config, epoch_manager, create_test_signer(), store.clone());
+
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: None };
+        conductor.certify(p0.clone()).await.unwrap();
+        conductor.certify(p1).await.unwrap();
+
+        // Evicted from the hot store but still served from the archive
+        assert!(store.hot().get_by_height(0).await.is_none());
+        assert_eq!(conductor.get_by_height(0).await, Ok(Some(p0)));
+    }
+
+    #[tokio::test]
+    async fn test_conductor_restores_snapshot() {
+        let config = ConductorConfig { quorum_threshold: 2, ..Default::default() };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let source: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config.clone(), epoch_manager.clone(), create_test_signer());
+        source.start().await;
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        source.commit(payload.clone()).await.unwrap();
+        source.acknowledge(ack(1, &payload)).await.unwrap();
+        source.acknowledge(ack(2, &payload)).await.unwrap();
+
+        let bytes = source.snapshot().await.encode();
+        let snapshot = Snapshot::decode_cfg(bytes.as_ref(), &2).unwrap();
+
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        conductor.start().await;
+        conductor.restore(snapshot.clone()).await.unwrap();
+        assert_eq!(conductor.latest_certified_with_proof().await.map(|(p, _)| p), Some(payload));
+        assert_eq!(conductor.next_height().await, 1);
+        conductor.commit(TestPayload { data: vec![2], height: 1, parent: None }).await.unwrap();
+
+        // A snapshot that does not advance the chain is refused
+        assert!(matches!(
+            conductor.restore(snapshot).await,
+            Err(ConductorError::InvalidSnapshot(_))
+        ));
+    }
+
+    // Sync source that reports no tip once released, counting the syncs
+    // waiting on it
+    #[derive(Clone, Default)]
+    struct GatedSource {
+        waiting: Arc<AtomicUsize>,
+        gate: Arc<tokio::sync::Notify>,
+    }
+
+    impl SyncSource<TestPayload, ed25519::PublicKey> for GatedSource {
+        async fn tip(&self) -> Result<Option<Height>, SyncError> {
+            self.waiting.fetch_add(1, Ordering::AcqRel);
+            self.gate.notified().await;
+            Ok(None)
+        }
+
+        async fn fetch(
+            &self,
+            _from: Height,
+            _to: Height,
+        ) -> Result<Vec<crate::traits::SyncEntry<TestPayload, ed25519::PublicKey>>, SyncError>
+        {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conductor_overlapping_syncs() {
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(ConductorConfig::default(), epoch_manager, create_test_signer());
+        conductor.start().await;
+
+        let source = GatedSource::default();
+        let syncs: Vec<_> = (0..2)
+            .map(|_| {
+                let (conductor, source) = (conductor.clone(), source.clone());
+                tokio::spawn(async move { conductor.sync(&source).await })
+            })
+            .collect();
+        while source.waiting.load(Ordering::Acquire) < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        // Commits stay rejected until the last sync finishes
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        source.gate.notify_one();
+        let (first, _, mut rest) = futures::future::select_all(syncs).await;
+        assert_eq!(first.unwrap(), Ok(0));
+        assert!(matches!(conductor.commit(payload.clone()).await, Err(ConductorError::Syncing)));
+        source.gate.notify_one();
+        assert_eq!(rest.pop().unwrap().await.unwrap(), Ok(0));
+        conductor.commit(payload).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_conductor_sync() {
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let source: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(ConductorConfig::default(), epoch_manager.clone(), create_test_signer());
+        source.start().await;
+        for height in 0..5 {
+            let payload = TestPayload { data: vec![height as u8], height, parent: None };
+            source.commit(payload.clone()).await.unwrap();
+            source.acknowledge(ack(1, &payload)).await.unwrap();
+            source.acknowledge(ack(2, &payload)).await.unwrap();
+        }
+
+        let config = ConductorConfig { sync_batch_size: 2, ..Default::default() };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        conductor.start().await;
+        assert_eq!(conductor.sync(source.automaton()).await, Ok(5));
+        assert_eq!(conductor.latest().await, source.latest().await);
+        assert_eq!(conductor.certificate(4).await, source.certificate(4).await);
+        assert_eq!(conductor.sync(source.automaton()).await, Ok(0));
+
+        // Caught up, the node takes part again
+        conductor.commit(TestPayload { data: vec![5], height: 5, parent: None }).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_conductor_sync_rejects_unverified_payloads() {
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let source: PayloadAutomaton<TestPayload, ed25519::PublicKey> = PayloadAutomaton::new();
+        let payload = TestPayload { data: vec![0], height: 0, parent: None };
+        source.certify(payload.clone(), 0).await.unwrap();
+
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(ConductorConfig::default(), epoch_manager.clone(), create_test_signer());
+        let result = conductor.sync(&source).await;
+        assert_eq!(result, Err(ConductorError::Sync(SyncError::MissingCertificate { height: 0 })));
+
+        // A certificate signed by too few validators does not verify
+        let mut signatures = BTreeMap::new();
+        let signed = ack(1, &payload);
+        signatures.insert(signed.validator, signed.signature);
+        let certificate =
+            Certificate { epoch: 0, height: 0, digest: payload.digest(), signatures, chunk: None };
+        source.store().store_certificate(0, &certificate.encode()).await.unwrap();
+        let result = conductor.sync(&source).await;
+        assert_eq!(result, Err(ConductorError::Sync(SyncError::InvalidCertificate { height: 0 })));
+        assert_eq!(conductor.latest().await, None);
+
+        // A quorum certificate from an epoch not reached yet does not verify
+        let mut signatures = BTreeMap::new();
+        for seed in 1..=2 {
+            let signed = Acknowledgment::sign(&validator(seed), 1, 0, payload.digest());
+            signatures.insert(signed.validator, signed.signature);
+        }
+        let certificate =
+            Certificate { epoch: 1, height: 0, digest: payload.digest(), signatures, chunk: None };
+        source.store().store_certificate(0, &certificate.encode()).await.unwrap();
+        let result = conductor.sync(&source).await;
+        assert_eq!(result, Err(ConductorError::Sync(SyncError::InvalidCertificate { height: 0 })));
+
+        // Nor does one from an epoch without a known quorum threshold
+        let mut signatures = BTreeMap::new();
+        for seed in 1..=2 {
+            let signed = ack(seed, &payload);
+            signatures.insert(signed.validator, signed.signature);
+        }
+        let certificate =
+            Certificate { epoch: 0, height: 0, digest: payload.digest(), signatures, chunk: None };
+        source.store().store_certificate(0, &certificate.encode()).await.unwrap();
+        let quorumless: Conductor<TestPayload, QuorumlessEpochManager, MockSigner> = Conductor::new(
+            ConductorConfig::default(),
+            QuorumlessEpochManager(epoch_manager.clone()),
+            create_test_signer(),
+        );
+        let result = quorumless.sync(&source).await;
+        assert_eq!(result, Err(ConductorError::Sync(SyncError::InvalidCertificate { height: 0 })));
+        assert_eq!(conductor.sync(&source).await, Ok(1));
+
+        let config = ConductorConfig { sync_trust_uncertified: true, ..Default::default() };
+        let trusting: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        let fresh: PayloadAutomaton<TestPayload, ed25519::PublicKey> = PayloadAutomaton::new();
+        fresh.certify(payload.clone(), 0).await.unwrap();
+        assert_eq!(trusting.sync(&fresh).await, Ok(1));
+        assert_eq!(trusting.latest().await, Some(payload));
+    }
+
+    #[tokio::test]
+    async fn test_conductor_reports_equivocation() {
+        let config = ConductorConfig { quorum_threshold: 2, ..Default::default() };
+        let epoch_manager = MockEpochManager { is_sequencer: true, changes: None };
+        let conductor: Conductor<TestPayload, MockEpochManager, MockSigner> =
+            Conductor::new(config, epoch_manager, create_test_signer());
+        conductor.start().await;
+        let mut equivocations = conductor.subscribe_equivocations();
+
+        let payload = TestPayload { data: vec![1], height: 0, parent: None };
+        let conflicting = TestPayload { data: vec![2], height: 0, parent: None };
+        conductor.commit(payload.clone()).await.unwrap();
+        conductor.acknowledge(ack(1, &payload)).await.unwrap();
+        assert!(conductor.acknowledge(ack(1, &conflicting)).await.unwrap().is_empty());
+
+        let equivocation = equivocations.next().await.unwrap();
+        assert_eq!(
+            equivocation,
+            Equivocation::Validator { first: ack(1, &payload), second: ack(1, &conflicting) }
+        );
+        assert!(equivocation.verify());
+        assert_eq!(conductor.equivocations().await, vec![equivocation]);
+
+        // The equivocating validator's first acknowledgment still counts
+        let certified = conductor.acknowledge(ack(2, &payload)).await.unwrap();
+        assert_eq!(certified, vec![payload]);
+    }
 }
@@ -0,0 +1,504 @@
+//! commonware ordered_broadcast engine integration.
+//!
+//! This module provides the pieces [`Conductor::spawn_engine`] wires into a
+//! commonware [`ordered_broadcast`] engine:
+//!
+//! - [`EngineConfig`]: Tunables for the engine and its journal
+//! - [`EpochMonitor`]: Feeds conductor epoch changes to the engine
+//! - [`PayloadRelay`]: Broadcasts payload contents alongside chunks
+//! - [`PayloadReporter`]: Certifies payloads when chunks are certified
+//!
+//! With an engine running, committed payloads are proposed as chunks while
+//! the local node is the sequencer, validators sign those chunks over the
+//! network once the relayed payload checks out, and every node certifies a
+//! payload once the engine assembles a quorum certificate over its chunk.
+//!
+//! [`Conductor::spawn_engine`]: crate::Conductor::spawn_engine
+//! [`ordered_broadcast`]: commonware_consensus::ordered_broadcast
+
+use std::{
+    marker::PhantomData,
+    num::{NonZeroU16, NonZeroU64, NonZeroUsize},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use commonware_codec::Encode as _;
+use commonware_consensus::{
+    Monitor, Relay, Reporter,
+    ordered_broadcast::{scheme::Scheme, types::Activity},
+    types::Epoch as ConsensusEpoch,
+};
+use commonware_cryptography::PublicKey;
+use commonware_p2p::{Receiver, Recipients, Sender};
+use futures::channel::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::{
+    automaton::PayloadAutomaton,
+    traits::{Payload, PayloadStore},
+    types::{ChunkProof, Epoch},
+};
+
+/// Configuration for the ordered_broadcast engine.
+#[derive(Debug, Clone)]
+pub struct EngineConfig {
+    /// Namespace chunks and acks are signed under.
+    ///
+    /// Must match across all participants.
+    pub namespace: Vec<u8>,
+    /// How often a proposal is rebroadcast if no quorum is reached.
+    pub rebroadcast_timeout: Duration,
+    /// Number of past and future epochs to accept messages for.
+    pub epoch_bounds: (u64, u64),
+    /// Number of chunk heights past a sequencer's tip to accept acks for.
+    pub height_bound: u64,
+    /// Whether proposals are sent as priority messages.
+    pub priority_proposals: bool,
+    /// Whether acks are sent as priority messages.
+    pub priority_acks: bool,
+    /// Prefix for the engine's per-sequencer journal names.
+    pub journal_name_prefix: String,
+    /// Number of chunk heights per journal section.
+    pub journal_heights_per_section: NonZeroU64,
+    /// Number of bytes to buffer when replaying a journal.
+    pub journal_replay_buffer: NonZeroUsize,
+    /// Size of the write buffer for each journal blob.
+    pub journal_write_buffer: NonZeroUsize,
+    /// Compression level for the journal, if any.
+    pub journal_compression: Option<u8>,
+    /// Page size of the journal buffer pool.
+    pub journal_page_size: NonZeroU16,
+    /// Number of pages cached by the journal buffer pool.
+    pub journal_page_cache_size: NonZeroUsize,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            namespace: b"_ARTURO".to_vec(),
+            rebroadcast_timeout: Duration::from_secs(1),
+            epoch_bounds: (1, 1),
+            height_bound: 2,
+            priority_proposals: false,
+            priority_acks: false,
+            journal_name_prefix: "arturo-sequencer-".to_string(),
+            journal_heights_per_section: NonZeroU64::new(1024).expect("non-zero"),
+            journal_replay_buffer: NonZeroUsize::new(64 * 1024).expect("non-zero"),
+            journal_write_buffer: NonZeroUsize::new(64 * 1024).expect("non-zero"),
+            journal_compression: None,
+            journal_page_size: NonZeroU16::new(4096).expect("non-zero"),
+            journal_page_cache_size: NonZeroUsize::new(256).expect("non-zero"),
+        }
+    }
+}
+
+/// Shared state of an [`EpochMonitor`].
+struct MonitorState {
+    /// The latest epoch.
+    epoch: Epoch,
+    /// Engines subscribed to epoch updates.
+    subscribers: Vec<mpsc::Sender<ConsensusEpoch>>,
+}
+
+/// A commonware [`Monitor`] over the conductor's epoch.
+///
+/// The conductor updates the monitor on every epoch change so the engine
+/// tracks the same epoch as the conductor.
+#[derive(Clone)]
+pub struct EpochMonitor {
+    state: Arc<Mutex<MonitorState>>,
+}
+
+impl std::fmt::Debug for EpochMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock().expect("monitor lock poisoned");
+        f.debug_struct("EpochMonitor").field("epoch", &state.epoch).finish_non_exhaustive()
+    }
+}
+
+impl EpochMonitor {
+    /// Creates a monitor starting at `epoch`.
+    pub fn new(epoch: Epoch) -> Self {
+        Self { state: Arc::new(Mutex::new(MonitorState { epoch, subscribers: Vec::new() })) }
+    }
+
+    /// Returns the latest epoch.
+    pub fn epoch(&self) -> Epoch {
+        self.state.lock().expect("monitor lock poisoned").epoch
+    }
+
+    /// Advances the monitor to `epoch` and notifies subscribers.
+    ///
+    /// Epochs never move backwards; older epochs are ignored.
+    pub fn update(&self, epoch: Epoch) {
+        let mut state = self.state.lock().expect("monitor lock poisoned");
+        if epoch < state.epoch {
+            return;
+        }
+        state.epoch = epoch;
+
+        // Subscribers only need the latest epoch, so a full channel can skip
+        // an update
+        state.subscribers.retain(|subscriber| !subscriber.is_closed());
+        for subscriber in &mut state.subscribers {
+            let _ = subscriber.try_send(ConsensusEpoch::new(epoch));
+        }
+    }
+}
+
+impl Monitor for EpochMonitor {
+    type Index = ConsensusEpoch;
+
+    async fn subscribe(&mut self) -> (Self::Index, mpsc::Receiver<Self::Index>) {
+        let (tx, rx) = mpsc::channel(1);
+        let mut state = self.state.lock().expect("monitor lock poisoned");
+        state.subscribers.push(tx);
+        (ConsensusEpoch::new(state.epoch), rx)
+    }
+}
+
+/// A commonware [`Reporter`] that certifies payloads on quorum certificates.
+///
+/// On every [`Activity::Lock`] the payload carried by the chunk is written to
+/// the automaton's store: the sequencer certifies its own pending payload,
+/// and validators certify the payload relayed to them, so every node's
+/// latest certified payload advances with the engine.
+pub struct PayloadReporter<P: Payload, K: PublicKey, St, S> {
+    automaton: PayloadAutomaton<P, K, St>,
+    _scheme: PhantomData<S>,
+}
+
+impl<P: Payload, K: PublicKey, St: Clone, S> Clone for PayloadReporter<P, K, St, S> {
+    fn clone(&self) -> Self {
+        Self { automaton: self.automaton.clone(), _scheme: PhantomData }
+    }
+}
+
+impl<P: Payload, K: PublicKey, St, S> std::fmt::Debug for PayloadReporter<P, K, St, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadReporter").finish_non_exhaustive()
+    }
+}
+
+impl<P: Payload, K: PublicKey, St, S> PayloadReporter<P, K, St, S> {
+    /// Creates a reporter that certifies payloads on `automaton`.
+    pub const fn new(automaton: PayloadAutomaton<P, K, St>) -> Self {
+        Self { automaton, _scheme: PhantomData }
+    }
+}
+
+impl<P, K, St, S> Reporter for PayloadReporter<P, K, St, S>
+where
+    P: Payload,
+    K: PublicKey,
+    St: PayloadStore<P>,
+    S: Scheme<K, P::Digest>,
+{
+    type Activity = Activity<K, S, P::Digest>;
+
+    async fn report(&mut self, activity: Self::Activity) {
+        let Activity::Lock(lock) = activity else {
+            return;
+        };
+        let proof = ChunkProof {
+            sequencer: lock.chunk.sequencer.clone(),
+            height: lock.chunk.height.get(),
+            certificate: lock.certificate.encode().to_vec(),
+        };
+        match self.automaton.certify_digest(&lock.chunk.payload, lock.epoch.get(), proof).await {
+            Ok(Some(payload)) => {
+                info!(height = payload.height(), epoch = %lock.epoch, "payload certified");
+            }
+            Ok(None) => {
+                debug!(chunk = %lock.chunk.height, "certified chunk was not applied");
+            }
+            Err(e) => warn!(error = %e, "failed to store certified payload"),
+        }
+    }
+}
+
+/// A commonware [`Relay`] that broadcasts payload contents to all peers.
+///
+/// The engine only carries digests; the relay sends the encoded payload,
+/// prefixed with the big-endian epoch it was proposed in, over a dedicated
+/// channel so validators can verify the chunk. Peers feed what they receive
+/// to [`PayloadRelay::run`].
+pub struct PayloadRelay<P: Payload, K: PublicKey, St, Snd> {
+    automaton: PayloadAutomaton<P, K, St>,
+    sender: Snd,
+}
+
+impl<P: Payload, K: PublicKey, St: Clone, Snd: Clone> Clone for PayloadRelay<P, K, St, Snd> {
+    fn clone(&self) -> Self {
+        Self { automaton: self.automaton.clone(), sender: self.sender.clone() }
+    }
+}
+
+impl<P: Payload, K: PublicKey, St, Snd> std::fmt::Debug for PayloadRelay<P, K, St, Snd> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadRelay").finish_non_exhaustive()
+    }
+}
+
+impl<P, K, St, Snd> PayloadRelay<P, K, St, Snd>
+where
+    P: Payload,
+    K: PublicKey,
+    St: PayloadStore<P>,
+    Snd: Sender<PublicKey = K>,
+{
+    /// Creates a relay that broadcasts `automaton`'s payloads over `sender`.
+    pub const fn new(automaton: PayloadAutomaton<P, K, St>, sender: Snd) -> Self {
+        Self { automaton, sender }
+    }
+
+    /// Hands payloads relayed by peers to the automaton.
+    ///
+    /// Runs until `receiver` is closed. Messages that do not decode are
+    /// dropped. The epoch a payload was relayed in is what a sequencer
+    /// relaying conflicting payloads is reported under.
+    pub async fn run(&self, mut receiver: impl Receiver<PublicKey = K>) {
+        loop {
+            let (sender, message) = match receiver.recv().await {
+                Ok(message) => message,
+                Err(e) => {
+                    debug!(error = ?e, "payload receiver closed");
+                    return;
+                }
+            };
+            let Some((epoch, payload)) =
+                message.split_first_chunk().and_then(|(epoch, payload)| {
+                    Some((Epoch::from_be_bytes(*epoch), P::decode(payload)?))
+                })
+            else {
+                debug!(?sender, "dropping undecodable payload");
+                continue;
+            };
+            self.automaton.receive(sender, epoch, payload).await;
+        }
+    }
+}
+
+impl<P, K, St, Snd> Relay for PayloadRelay<P, K, St, Snd>
+where
+    P: Payload,
+    K: PublicKey,
+    St: PayloadStore<P>,
+    Snd: Sender<PublicKey = K>,
+{
+    type Digest = P::Digest;
+
+    async fn broadcast(&mut self, digest: Self::Digest) {
+        let Some((payload, epoch)) = self.automaton.proposal(&digest).await else {
+            warn!(?digest, "no payload to relay");
+            return;
+        };
+        let mut message = epoch.to_be_bytes().to_vec();
+        message.extend(payload.encode());
+        if let Err(e) = self.sender.send(Recipients::All, Bytes::from(message), false).await {
+            warn!(error = ?e, height = payload.height(), "failed to relay payload");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use commonware_cryptography::{Signer as _, ed25519};
+    use commonware_p2p::simulated::{Config as NetworkConfig, Link, Network};
+    use commonware_runtime::{Clock as _, Metrics as _, Quota, Runner as _, deterministic};
+    use futures::{StreamExt as _, stream};
+
+    use super::*;
+    use crate::{
+        Conductor, ConductorConfig, ConductorError, EpochManager, EpochStream, SyncError,
+        providers::Ed25519ValidatorsProvider, test_utils::TestPayload, types::TransferError,
+    };
+
+    // Static cluster where seed 0 is the sequencer and every node validates
+    #[derive(Clone)]
+    struct ClusterEpochManager {
+        validators: Vec<ed25519::PublicKey>,
+    }
+
+    impl EpochManager for ClusterEpochManager {
+        type PublicKey = ed25519::PublicKey;
+
+        fn current_epoch(&self) -> Epoch {
+            0
+        }
+
+        fn sequencer(&self, _epoch: Epoch) -> Option<Self::PublicKey> {
+            self.validators.first().cloned()
+        }
+
+        async fn transfer_leader(&self) -> Result<(), TransferError> {
+            Err(TransferError::NotSupported)
+        }
+
+        fn subscribe(&self) -> EpochStream<Self::PublicKey> {
+            Box::pin(stream::empty())
+        }
+
+        fn validators(&self, _epoch: Epoch) -> Option<Vec<Self::PublicKey>> {
+            Some(self.validators.clone())
+        }
+
+        fn quorum_threshold(&self, _epoch: Epoch) -> Option<usize> {
+            Some(self.validators.len())
+        }
+    }
+
+    #[test]
+    fn test_monitor_notifies_subscribers() {
+        futures::executor::block_on(async {
+            let mut monitor = EpochMonitor::new(3);
+            let (epoch, mut updates) = monitor.subscribe().await;
+            assert_eq!(epoch, ConsensusEpoch::new(3));
+
+            monitor.update(2);
+            monitor.update(5);
+            assert_eq!(monitor.epoch(), 5);
+            assert_eq!(updates.next().await, Some(ConsensusEpoch::new(5)));
+        });
+    }
+
+    #[test]
+    fn test_engine_certifies_committed_payloads() {
+        // The conductors' epoch change tasks run on tokio
+        let tokio = tokio::runtime::Runtime::new().unwrap();
+        let _guard = tokio.enter();
+
+        let runner = deterministic::Runner::timed(Duration::from_secs(30));
+        runner.start(|context| async move {
+            let signers: Vec<_> = (0..3).map(ed25519::PrivateKey::from_seed).collect();
+            let keys: Vec<_> = signers.iter().map(|signer| signer.public_key()).collect();
+
+            let (network, oracle) = Network::new(
+                context.with_label("network"),
+                NetworkConfig {
+                    max_size: 1024 * 1024,
+                    disconnect_on_block: true,
+                    tracked_peer_sets: None,
+                },
+            );
+            network.start();
+            for a in &keys {
+                for b in keys.iter().filter(|b| *b != a) {
+                    let link = Link {
+                        latency: Duration::from_millis(10),
+                        jitter: Duration::ZERO,
+                        success_rate: 1.0,
+                    };
+                    oracle.add_link(a.clone(), b.clone(), link).await.unwrap();
+                }
+            }
+
+            let mut conductors = Vec::new();
+            for (i, signer) in signers.into_iter().enumerate() {
+                let control = oracle.control(signer.public_key());
+                let quota = Quota::per_second(NonZeroU32::MAX);
+                let chunks = control.register(0, quota).await.unwrap();
+                let acks = control.register(1, quota).await.unwrap();
+                let payloads = control.register(2, quota).await.unwrap();
+
+                let epoch_manager = ClusterEpochManager { validators: keys.clone() };
+                let config = EngineConfig::default();
+                let validators = Ed25519ValidatorsProvider::new(
+                    epoch_manager.clone(),
+                    &config.namespace,
+                    signer.clone(),
+                );
+                let conductor: Conductor<TestPayload, ClusterEpochManager, ed25519::PrivateKey> =
+                    Conductor::new(ConductorConfig::default(), epoch_manager, signer);
+                conductor.start().await;
+                conductor.spawn_engine(
+                    context.with_label(&format!("engine_{i}")),
+                    config,
+                    validators,
+                    chunks,
+                    acks,
+                    payloads,
+                );
+                conductors.push(conductor);
+            }
+
+            let sequencer = &conductors[0];
+            for height in 0..3 {
+                sequencer
+                    .commit(TestPayload { data: vec![height as u8], height, parent: None })
+                    .await
+                    .unwrap();
+            }
+
+            // No manual acknowledgments: the engine signs and certifies chunks
+            // and every validator picks up the certified payloads
+            for conductor in &conductors {
+                while conductor.latest().await.map(|p| p.height) != Some(2) {
+                    context.sleep(Duration::from_millis(50)).await;
+                }
+                assert_eq!(
+                    conductor.get_by_height(1).await.map(|p| p.map(|p| p.data)),
+                    Ok(Some(vec![1]))
+                );
+
+                // Each payload is proven by the engine's certificate over its chunk
+                for height in 0..3 {
+                    let certificate = conductor.certificate(height).await.unwrap();
+                    assert_eq!(certificate.height, height);
+                    assert!(certificate.signatures.is_empty());
+                    let chunk = certificate.chunk.unwrap();
+                    assert_eq!(chunk.sequencer, keys[0]);
+                    assert_eq!(chunk.height, height);
+                    assert!(!chunk.certificate.is_empty());
+                }
+                let (latest, _) = conductor.latest_certified_with_proof().await.unwrap();
+                assert_eq!(latest.height, 2);
+            }
+            assert_eq!(sequencer.pending_count().await, 0);
+
+            // A node that fell behind checks the engine's proofs while syncing
+            // once its own engine knows the validators' signing scheme
+            let late_signer = ed25519::PrivateKey::from_seed(3);
+            let control = oracle.control(late_signer.public_key());
+            let quota = Quota::per_second(NonZeroU32::MAX);
+            let chunks = control.register(0, quota).await.unwrap();
+            let acks = control.register(1, quota).await.unwrap();
+            let payloads = control.register(2, quota).await.unwrap();
+            let epoch_manager = ClusterEpochManager { validators: keys.clone() };
+            let config = EngineConfig::default();
+            let validators = Ed25519ValidatorsProvider::new(
+                epoch_manager.clone(),
+                &config.namespace,
+                late_signer.clone(),
+            );
+            let late: Conductor<TestPayload, ClusterEpochManager, ed25519::PrivateKey> =
+                Conductor::new(ConductorConfig::default(), epoch_manager, late_signer);
+            assert_eq!(
+                late.sync(conductors[1].automaton()).await,
+                Err(ConductorError::Sync(SyncError::InvalidCertificate { height: 0 }))
+            );
+            late.spawn_engine(
+                context.with_label("engine_late"),
+                config,
+                validators,
+                chunks,
+                acks,
+                payloads,
+            );
+            assert_eq!(late.sync(conductors[1].automaton()).await, Ok(3));
+            assert_eq!(late.certificate(2).await, conductors[1].certificate(2).await);
+
+            // Stopping a validator stops its engine, so chunks no longer
+            // reach quorum
+            conductors[1].stop().await;
+            sequencer.commit(TestPayload { data: vec![3], height: 3, parent: None }).await.unwrap();
+            context.sleep(Duration::from_secs(5)).await;
+            assert_eq!(sequencer.latest().await.map(|p| p.height), Some(2));
+            assert_eq!(sequencer.pending_count().await, 1);
+        });
+    }
+}
@@ -20,11 +20,41 @@ pub use automaton::{PayloadAutomaton, PayloadContext};
 mod conductor;
 pub use conductor::{Conductor, ConductorConfig};
 
+mod engine;
+pub use engine::{EngineConfig, EpochMonitor, PayloadRelay, PayloadReporter};
+
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "metrics")]
+pub use metrics::Metrics;
+
 mod providers;
-pub use providers::{EpochSequencersProvider, StaticSequencersProvider, ValidatorsProvider};
+pub use providers::{
+    Ed25519ValidatorsProvider, EpochSequencersProvider, StaticSequencersProvider,
+    ValidatorsProvider,
+};
+
+mod receipt;
+pub use receipt::CommitReceipt;
+
+mod store;
+pub use store::{FilePayloadStore, FileStoreConfig, InMemoryPayloadStore, TieredPayloadStore};
 
 mod traits;
-pub use traits::{EpochManager, EpochStream, Payload, PayloadStore, StoreError};
+pub use traits::{
+    CertifiedStream, EpochManager, EpochStream, EquivocationStream, Payload, PayloadStore,
+    PayloadValidator, StoreError, SyncEntry, SyncError, SyncSource,
+};
 
 mod types;
-pub use types::{ConductorError, Epoch, EpochChange, Height, PendingPayload, TransferError};
+pub use types::{
+    ACK_NAMESPACE, Acknowledgment, BackpressurePolicy, Certificate, CertifiedEvent,
+    CertifiedPayload, ChunkProof, Committed, ConductorError, Epoch, EpochChange, Equivocation,
+    Height, PendingPayload, QueueStatus, RetentionPolicy, Snapshot, TransferError, ValidationError,
+};
+
+mod validator;
+pub use validator::ValidatorChain;
+
+#[cfg(test)]
+mod test_utils;
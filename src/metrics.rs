@@ -0,0 +1,198 @@
+//! Prometheus metrics.
+//!
+//! This module provides [`Metrics`], the counters and gauges a
+//! [`Conductor`] updates as it commits and certifies payloads and follows
+//! epoch changes. Register them with a [`Registry`] to export them.
+//!
+//! [`Conductor`]: crate::Conductor
+
+use std::time::Duration;
+
+use prometheus_client::{
+    encoding::EncodeLabelSet,
+    metrics::{
+        counter::Counter,
+        family::Family,
+        gauge::Gauge,
+        histogram::{Histogram, exponential_buckets},
+    },
+    registry::Registry,
+};
+
+use crate::types::{ConductorError, Epoch};
+
+/// Labels of a rejected commit.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RejectedLabels {
+    /// The [`ConductorError`] variant, in snake case.
+    error: &'static str,
+}
+
+/// Metrics for a conductor and its automaton.
+///
+/// Clones share the same underlying metrics.
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    commits_accepted: Counter,
+    commits_rejected: Family<RejectedLabels, Counter>,
+    certification_latency: Histogram,
+    acks_received: Counter,
+    pending: Gauge,
+    epoch: Gauge,
+    leader: Gauge,
+    epoch_changes: Counter,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            commits_accepted: Counter::default(),
+            commits_rejected: Family::default(),
+            // 5ms up to about 40s
+            certification_latency: Histogram::new(exponential_buckets(0.005, 2.0, 14)),
+            acks_received: Counter::default(),
+            pending: Gauge::default(),
+            epoch: Gauge::default(),
+            leader: Gauge::default(),
+            epoch_changes: Counter::default(),
+        }
+    }
+}
+
+impl Metrics {
+    /// Registers every metric with `registry`.
+    ///
+    /// Use [`Registry::sub_registry_with_prefix`] to namespace them.
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "commits_accepted",
+            "Commits submitted for certification",
+            self.commits_accepted.clone(),
+        );
+        registry.register(
+            "commits_rejected",
+            "Commits rejected, by error",
+            self.commits_rejected.clone(),
+        );
+        registry.register(
+            "certification_latency_seconds",
+            "Time from submitting a payload to its certification",
+            self.certification_latency.clone(),
+        );
+        registry.register(
+            "acks_received",
+            "Acknowledgments recorded for pending payloads",
+            self.acks_received.clone(),
+        );
+        registry.register(
+            "pending_payloads",
+            "Committed payloads awaiting certification",
+            self.pending.clone(),
+        );
+        registry.register("epoch", "Current epoch", self.epoch.clone());
+        registry.register("leader", "Whether this node is the sequencer", self.leader.clone());
+        registry.register("epoch_changes", "Epoch changes applied", self.epoch_changes.clone());
+    }
+
+    /// Records the outcome of a commit.
+    pub(crate) fn commit<T>(&self, result: &Result<T, ConductorError>) {
+        match result {
+            Ok(_) => {
+                self.commits_accepted.inc();
+            }
+            Err(error) => {
+                self.commits_rejected.get_or_create(&RejectedLabels { error: kind(error) }).inc();
+            }
+        }
+    }
+
+    /// Records a payload certified `latency` after it was submitted.
+    pub(crate) fn certified(&self, latency: Duration) {
+        self.certification_latency.observe(latency.as_secs_f64());
+    }
+
+    /// Records an acknowledgment.
+    pub(crate) fn ack_received(&self) {
+        self.acks_received.inc();
+    }
+
+    /// Records the number of payloads awaiting certification.
+    pub(crate) fn pending(&self, depth: usize) {
+        self.pending.set(i64::try_from(depth).unwrap_or(i64::MAX));
+    }
+
+    /// Records the current epoch and whether this node leads it.
+    pub(crate) fn epoch(&self, epoch: Epoch, leader: bool) {
+        self.epoch.set(i64::try_from(epoch).unwrap_or(i64::MAX));
+        self.leader.set(leader.into());
+    }
+
+    /// Records a change to `epoch`, led by this node if `leader` is set.
+    pub(crate) fn epoch_changed(&self, epoch: Epoch, leader: bool) {
+        self.epoch_changes.inc();
+        self.epoch(epoch, leader);
+    }
+}
+
+/// Returns the label for `error`'s variant.
+const fn kind(error: &ConductorError) -> &'static str {
+    match error {
+        ConductorError::NotSequencer => "not_sequencer",
+        ConductorError::ValidationFailed(_) => "validation_failed",
+        ConductorError::InvalidHeight { .. } => "invalid_height",
+        ConductorError::ParentMismatch { .. } => "parent_mismatch",
+        ConductorError::WindowFull { .. } => "window_full",
+        ConductorError::Backpressure { .. } => "backpressure",
+        ConductorError::UnknownValidator { .. } => "unknown_validator",
+        ConductorError::InvalidSignature => "invalid_signature",
+        ConductorError::InvalidSnapshot(_) => "invalid_snapshot",
+        ConductorError::Pruned { .. } => "pruned",
+        ConductorError::StaleEpoch { .. } => "stale_epoch",
+        ConductorError::CommitTimeout { .. } => "commit_timeout",
+        ConductorError::EpochChanged { .. } => "epoch_changed",
+        ConductorError::Superseded { .. } => "superseded",
+        ConductorError::NotInitialized => "not_initialized",
+        ConductorError::ChannelClosed => "channel_closed",
+        ConductorError::Store(_) => "store",
+        ConductorError::Syncing => "syncing",
+        ConductorError::Sync(_) => "sync",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus_client::encoding::text::encode;
+
+    use super::*;
+
+    fn render(metrics: &Metrics) -> String {
+        let mut registry = Registry::with_prefix("arturo");
+        metrics.register(&mut registry);
+        let mut text = String::new();
+        encode(&mut text, &registry).unwrap();
+        text
+    }
+
+    #[test]
+    fn test_metrics_render() {
+        let metrics = Metrics::default();
+        metrics.commit(&Ok(()));
+        metrics.commit::<()>(&Err(ConductorError::Backpressure { depth: 8 }));
+        metrics.commit::<()>(&Err(ConductorError::NotSequencer));
+        metrics.certified(Duration::from_millis(20));
+        metrics.ack_received();
+        metrics.pending(3);
+        metrics.epoch_changed(4, true);
+
+        let text = render(&metrics);
+        assert!(text.contains("arturo_commits_accepted_total 1"));
+        assert!(text.contains(r#"arturo_commits_rejected_total{error="backpressure"} 1"#));
+        assert!(text.contains(r#"arturo_commits_rejected_total{error="not_sequencer"} 1"#));
+        assert!(text.contains("arturo_certification_latency_seconds_count 1"));
+        assert!(text.contains("arturo_acks_received_total 1"));
+        assert!(text.contains("arturo_pending_payloads 3"));
+        assert!(text.contains("arturo_epoch 4"));
+        assert!(text.contains("arturo_leader 1"));
+        assert!(text.contains("arturo_epoch_changes_total 1"));
+    }
+}
@@ -6,9 +6,10 @@
 use std::{collections::HashMap, sync::Arc};
 
 use commonware_consensus::{
-    ordered_broadcast::types::SequencersProvider, types::Epoch as ConsensusEpoch,
+    ordered_broadcast::{scheme::ed25519::Scheme as Ed25519Scheme, types::SequencersProvider},
+    types::Epoch as ConsensusEpoch,
 };
-use commonware_cryptography::PublicKey;
+use commonware_cryptography::{PublicKey, certificate::Provider, ed25519};
 use commonware_utils::ordered::Set;
 use tokio::sync::RwLock;
 
@@ -193,6 +194,68 @@ where
     }
 }
 
+/// An ed25519 validator signing scheme provider for ordered_broadcast.
+///
+/// Builds the scheme the engine uses to sign and verify chunk acks from the
+/// epoch manager's validator set. If the local key is not in an epoch's
+/// validator set, the scheme for that epoch can only verify.
+pub struct Ed25519ValidatorsProvider<E>
+where
+    E: EpochManager<PublicKey = ed25519::PublicKey>,
+{
+    epoch_manager: E,
+    namespace: Arc<[u8]>,
+    signer: ed25519::PrivateKey,
+}
+
+impl<E> Clone for Ed25519ValidatorsProvider<E>
+where
+    E: EpochManager<PublicKey = ed25519::PublicKey>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            epoch_manager: self.epoch_manager.clone(),
+            namespace: Arc::clone(&self.namespace),
+            signer: self.signer.clone(),
+        }
+    }
+}
+
+impl<E> std::fmt::Debug for Ed25519ValidatorsProvider<E>
+where
+    E: EpochManager<PublicKey = ed25519::PublicKey>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ed25519ValidatorsProvider").finish_non_exhaustive()
+    }
+}
+
+impl<E> Ed25519ValidatorsProvider<E>
+where
+    E: EpochManager<PublicKey = ed25519::PublicKey>,
+{
+    /// Creates a provider that signs acks with `signer` under `namespace`.
+    pub fn new(epoch_manager: E, namespace: &[u8], signer: ed25519::PrivateKey) -> Self {
+        Self { epoch_manager, namespace: namespace.into(), signer }
+    }
+}
+
+impl<E> Provider for Ed25519ValidatorsProvider<E>
+where
+    E: EpochManager<PublicKey = ed25519::PublicKey>,
+{
+    type Scope = ConsensusEpoch;
+    type Scheme = Ed25519Scheme;
+
+    fn scoped(&self, epoch: ConsensusEpoch) -> Option<Arc<Self::Scheme>> {
+        let validators = Set::from_iter_dedup(self.epoch_manager.validators(epoch.get())?);
+        let scheme =
+            Ed25519Scheme::signer(&self.namespace, validators.clone(), self.signer.clone())
+                .unwrap_or_else(|| Ed25519Scheme::verifier(&self.namespace, validators));
+        Some(Arc::new(scheme))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use commonware_cryptography::ed25519;
@@ -317,4 +380,33 @@ mod tests {
         let threshold = provider.quorum_threshold(0).unwrap();
         assert_eq!(threshold, 2);
     }
+
+    #[test]
+    fn test_ed25519_validators_provider() {
+        use commonware_cryptography::{Signer, certificate::Scheme as _};
+
+        let epoch_manager = MockEpochManager {
+            sequencer: create_test_public_key(1),
+            validators: vec![create_test_public_key(2), create_test_public_key(3)],
+        };
+
+        // A validator gets a signing scheme over the full validator set
+        let provider = Ed25519ValidatorsProvider::new(
+            epoch_manager.clone(),
+            b"test",
+            ed25519::PrivateKey::from_seed(2),
+        );
+        let scheme = provider.scoped(ConsensusEpoch::new(0)).unwrap();
+        assert_eq!(scheme.participants().len(), 2);
+        assert!(scheme.me().is_some());
+
+        // Any other key can only verify
+        let provider = Ed25519ValidatorsProvider::new(
+            epoch_manager,
+            b"test",
+            ed25519::PrivateKey::from_seed(1),
+        );
+        let scheme = provider.scoped(ConsensusEpoch::new(0)).unwrap();
+        assert!(scheme.me().is_none());
+    }
 }
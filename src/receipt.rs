@@ -0,0 +1,135 @@
+//! Commit receipts.
+//!
+//! This module provides [`CommitReceipt`], the future returned by
+//! [`Conductor::commit`] that resolves once the committed payload is
+//! certified.
+//!
+//! [`Conductor::commit`]: crate::Conductor::commit
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use commonware_cryptography::Digest;
+use tokio::{
+    sync::oneshot,
+    time::{Instant, Sleep},
+};
+
+use crate::types::{Committed, ConductorError, Height};
+
+/// Outcome delivered to a [`CommitReceipt`].
+pub(crate) type CommitOutcome<D> = Result<Committed<D>, ConductorError>;
+
+/// Sending half of a [`CommitReceipt`], held by the automaton until the
+/// committed payload is certified, superseded or dropped.
+pub(crate) type ReceiptSender<D> = oneshot::Sender<CommitOutcome<D>>;
+
+/// Future that resolves once a committed payload is certified.
+///
+/// Resolves with the height, digest and epoch the payload was certified in,
+/// or fails with:
+/// - [`ConductorError::CommitTimeout`] if the timeout elapses first
+/// - [`ConductorError::EpochChanged`] if an epoch change dropped the payload
+/// - [`ConductorError::Superseded`] if a different payload was certified at
+///   its height
+///
+/// Dropping the receipt stops waiting but does not withdraw the payload,
+/// which may still be certified. Likewise, a timed out payload stays in
+/// flight.
+#[derive(Debug)]
+pub struct CommitReceipt<D: Digest> {
+    height: Height,
+    digest: D,
+    receiver: oneshot::Receiver<CommitOutcome<D>>,
+    deadline: Option<Instant>,
+    /// Timer for `deadline`, created on first poll so a receipt can be
+    /// created outside a tokio runtime.
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<D: Digest> CommitReceipt<D> {
+    /// Creates a receipt for the payload at `height` with `digest`, and the
+    /// sender that resolves it.
+    pub(crate) fn new(height: Height, digest: D) -> (Self, ReceiptSender<D>) {
+        let (sender, receiver) = oneshot::channel();
+        (Self { height, digest, receiver, deadline: None, sleep: None }, sender)
+    }
+
+    /// Fails the receipt with [`ConductorError::CommitTimeout`] if the
+    /// payload is not certified within `timeout` from now.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Instant::now().checked_add(timeout);
+        self.sleep = None;
+        self
+    }
+
+    /// Returns the height of the committed payload.
+    pub const fn height(&self) -> Height {
+        self.height
+    }
+
+    /// Returns the digest of the committed payload.
+    pub const fn digest(&self) -> &D {
+        &self.digest
+    }
+}
+
+// The digest is never pinned; the timer is boxed
+impl<D: Digest> Unpin for CommitReceipt<D> {}
+
+impl<D: Digest> Future for CommitReceipt<D> {
+    type Output = CommitOutcome<D>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if let Poll::Ready(outcome) = Pin::new(&mut this.receiver).poll(cx) {
+            return Poll::Ready(outcome.unwrap_or(Err(ConductorError::ChannelClosed)));
+        }
+
+        let Some(deadline) = this.deadline else {
+            return Poll::Pending;
+        };
+        let sleep = this.sleep.get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+        if sleep.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Err(ConductorError::CommitTimeout { height: this.height }));
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commonware_cryptography::{Hasher as _, sha256};
+
+    use super::*;
+
+    fn receipt() -> (CommitReceipt<sha256::Digest>, ReceiptSender<sha256::Digest>) {
+        CommitReceipt::new(3, sha256::Sha256::hash(b"payload"))
+    }
+
+    #[tokio::test]
+    async fn test_receipt_resolves() {
+        let (receipt, sender) = receipt();
+        let committed = Committed { height: 3, digest: *receipt.digest(), epoch: 1 };
+        sender.send(Ok(committed)).unwrap();
+        assert_eq!(receipt.with_timeout(Duration::from_secs(5)).await, Ok(committed));
+    }
+
+    #[tokio::test]
+    async fn test_receipt_times_out() {
+        let (receipt, _sender) = receipt();
+        let result = receipt.with_timeout(Duration::from_millis(10)).await;
+        assert_eq!(result, Err(ConductorError::CommitTimeout { height: 3 }));
+    }
+
+    #[tokio::test]
+    async fn test_receipt_closed() {
+        let (receipt, sender) = receipt();
+        drop(sender);
+        assert_eq!(receipt.await, Err(ConductorError::ChannelClosed));
+    }
+}
@@ -0,0 +1,841 @@
+//! Durable, append-only file-backed payload store.
+//!
+//! Payloads and their certificates are appended to a sequence of segment
+//! files in a directory. Each record is framed as:
+//!
+//! ```text
+//! +-----------+-----------+------+-----------+------------------+
+//! | len (u32) | crc (u32) | kind | height    | encoded payload  |
+//! |           |           | (u8) | (u64)     | or certificate   |
+//! |           |           |      |           | (len - 9 bytes)  |
+//! +-----------+-----------+------+-----------+------------------+
+//! ```
+//!
+//! All integers are little-endian and the CRC32C covers everything after
+//! the checksum. On open, every segment is replayed to rebuild the height
+//! and digest indexes. A damaged record in the last segment with no intact
+//! record after it, such as one whose header or body runs past the end of
+//! the file or a run of zeros, is the result of a crash mid-write and is
+//! truncated away; any other damaged record is reported as corruption.
+//!
+//! Pruning appends a marker record, whose height is the retention point and
+//! whose data is empty, then deletes every sealed segment that no longer
+//! holds a live record. Replaying a marker drops the records before it that
+//! are below its height.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    ops::RangeBounds,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use commonware_cryptography::Crc32;
+use tracing::{debug, warn};
+
+use crate::{
+    traits::{Payload, PayloadStore, StoreError},
+    types::Height,
+};
+
+/// File extension used for segment files.
+const SEGMENT_EXTENSION: &str = "log";
+
+/// Size of the `len` and `crc` header fields.
+const HEADER_SIZE: usize = 8;
+
+/// Size of the `kind` and `height` fields preceding the record data.
+const PREFIX_SIZE: usize = 9;
+
+/// Record kind for a certified payload.
+const KIND_PAYLOAD: u8 = 0;
+
+/// Record kind for a payload's encoded certificate.
+const KIND_CERTIFICATE: u8 = 1;
+
+/// Record kind for a prune marker.
+const KIND_PRUNE: u8 = 2;
+
+/// Configuration for a [`FilePayloadStore`].
+#[derive(Debug, Clone)]
+pub struct FileStoreConfig {
+    /// Size in bytes after which a new segment file is started.
+    pub max_segment_size: u64,
+    /// Whether to `fsync` each segment after every append.
+    ///
+    /// Disabling this trades crash durability for write throughput.
+    pub sync_writes: bool,
+}
+
+impl Default for FileStoreConfig {
+    fn default() -> Self {
+        Self { max_segment_size: 64 * 1024 * 1024, sync_writes: true }
+    }
+}
+
+/// Location of a record within the segment log.
+#[derive(Debug, Clone, Copy)]
+struct Location {
+    /// Segment index.
+    segment: u64,
+    /// Byte offset of the record header within the segment.
+    offset: u64,
+    /// Length of the record body (excluding the header).
+    len: u32,
+}
+
+/// Index entry for a stored payload.
+#[derive(Debug, Clone, Copy)]
+struct Entry<D> {
+    /// Where the record lives.
+    location: Location,
+    /// Digest of the payload, kept to maintain the digest index.
+    digest: D,
+}
+
+/// Mutable state of the file store.
+struct Inner<P: Payload> {
+    /// Directory holding the segment files.
+    dir: PathBuf,
+    /// Store configuration.
+    config: FileStoreConfig,
+    /// Index of the segment currently being appended to.
+    active: u64,
+    /// Handle to the active segment, opened for appending.
+    writer: File,
+    /// Current size of the active segment.
+    active_size: u64,
+    /// Index entries by height.
+    by_height: BTreeMap<Height, Entry<P::Digest>>,
+    /// Heights indexed by payload digest.
+    by_digest: HashMap<P::Digest, Height>,
+    /// Certificate records by height.
+    certificates: BTreeMap<Height, Location>,
+    /// Set when a failed append could not be rolled back, leaving a partial
+    /// record at the tail of the active segment.
+    failed: bool,
+}
+
+/// A durable [`PayloadStore`] backed by an append-only segment log.
+///
+/// Survives process restarts: reopening the same directory rebuilds the
+/// height and digest indexes from disk, discarding any record torn by a
+/// crash. Storing a payload at a height that is already present appends a
+/// new record that supersedes the previous one and its certificate. Pruning
+/// reclaims disk space a whole segment at a time, so a segment is only
+/// deleted once every record in it is pruned or superseded; pruning below a
+/// height that frees no segment keeps everything and returns nothing.
+///
+/// File IO, including the `fsync` after each append when
+/// [`FileStoreConfig::sync_writes`] is set, is performed synchronously on
+/// the calling task and blocks its executor thread. The automaton stores
+/// certified payloads while holding its state lock, so a slow disk stalls
+/// every other conductor call for the duration of the write. Run the
+/// conductor on a multi-threaded runtime and keep the store on local disk.
+///
+/// If an append fails, the partial record is truncated away. If that also
+/// fails, the store refuses further writes until it is reopened, which
+/// discards the partial record as a torn tail.
+///
+/// # Example
+///
+/// ```ignore
+/// use arturo::{Conductor, FilePayloadStore};
+///
+/// let store = FilePayloadStore::open("./data/payloads")?;
+/// let conductor = Conductor::with_store(config, epoch_manager, signer, store);
+/// ```
+pub struct FilePayloadStore<P: Payload> {
+    inner: Arc<Mutex<Inner<P>>>,
+}
+
+impl<P: Payload> Clone for FilePayloadStore<P> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<P: Payload> std::fmt::Debug for FilePayloadStore<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.lock().expect("file store lock poisoned");
+        f.debug_struct("FilePayloadStore")
+            .field("dir", &inner.dir)
+            .field("segments", &(inner.active + 1))
+            .field("payloads", &inner.by_height.len())
+            .finish()
+    }
+}
+
+impl<P: Payload> FilePayloadStore<P> {
+    /// Opens (or creates) a store in `dir` with the default configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or read, or if a
+    /// segment holds a corrupt record other than a torn tail, i.e. one in a
+    /// sealed segment or followed by an intact record.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Self::open_with_config(dir, FileStoreConfig::default())
+    }
+
+    /// Opens (or creates) a store in `dir` with the given configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or read, or if a
+    /// segment holds a corrupt record other than a torn tail, i.e. one in a
+    /// sealed segment or followed by an intact record.
+    pub fn open_with_config(
+        dir: impl AsRef<Path>,
+        config: FileStoreConfig,
+    ) -> Result<Self, StoreError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(io_error)?;
+
+        let segments = list_segments(&dir)?;
+        let mut by_height = BTreeMap::new();
+        let mut by_digest = HashMap::new();
+        let mut certificates = BTreeMap::new();
+
+        let last = segments.last().copied().unwrap_or(0);
+        let mut active_size = 0;
+        for &segment in &segments {
+            let path = segment_path(&dir, segment);
+            let mut bytes = Vec::new();
+            File::open(&path).and_then(|mut f| f.read_to_end(&mut bytes)).map_err(io_error)?;
+
+            let mut offset = 0;
+            while offset < bytes.len() {
+                let Some(record) = parse_record(&bytes[offset..]) else {
+                    // Damage with no intact record after it is a crash
+                    // mid-write
+                    if segment != last || has_record(&bytes[offset + 1..]) {
+                        return Err(StoreError::Backend(format!(
+                            "corrupt record in segment {} at offset {offset}",
+                            path.display()
+                        )));
+                    }
+                    warn!(
+                        segment = %path.display(),
+                        offset,
+                        discarded = bytes.len() - offset,
+                        "truncating torn tail record"
+                    );
+                    let file = OpenOptions::new().write(true).open(&path).map_err(io_error)?;
+                    file.set_len(offset as u64).map_err(io_error)?;
+                    file.sync_all().map_err(io_error)?;
+                    break;
+                };
+
+                let len = record.len;
+                let location = Location { segment, offset: offset as u64, len };
+                let height = record.height;
+                match record.kind {
+                    KIND_CERTIFICATE => {
+                        certificates.insert(height, location);
+                    }
+                    KIND_PRUNE => {
+                        prune_indexes(&mut by_height, &mut by_digest, &mut certificates, height);
+                    }
+                    _ => {
+                        let payload = P::decode(record.data).ok_or_else(|| {
+                            StoreError::Backend(format!(
+                                "undecodable payload in segment {} at offset {offset}",
+                                path.display()
+                            ))
+                        })?;
+                        let digest = payload.digest();
+                        if let Some(previous) = by_height.insert(height, Entry { location, digest })
+                        {
+                            by_digest.remove(&previous.digest);
+                        }
+                        // The certificate may outlive a superseded payload
+                        // whose segment was deleted
+                        certificates.remove(&height);
+                        by_digest.insert(digest, height);
+                    }
+                }
+                offset += HEADER_SIZE + len as usize;
+            }
+
+            if segment == last {
+                active_size = offset as u64;
+            }
+        }
+
+        let writer = open_segment(&dir, last)?;
+        debug!(
+            dir = %dir.display(),
+            segments = segments.len(),
+            payloads = by_height.len(),
+            certificates = certificates.len(),
+            "opened file payload store"
+        );
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                dir,
+                config,
+                active: last,
+                writer,
+                active_size,
+                by_height,
+                by_digest,
+                certificates,
+                failed: false,
+            })),
+        })
+    }
+
+    /// Reads and decodes the payload record at `location`.
+    fn read(inner: &Inner<P>, location: Location) -> Option<P> {
+        Self::read_data(inner, location).and_then(|data| P::decode(&data))
+    }
+
+    /// Reads the data of the record at `location`.
+    fn read_data(inner: &Inner<P>, location: Location) -> Option<Vec<u8>> {
+        let path = segment_path(&inner.dir, location.segment);
+        let mut buf = vec![0u8; HEADER_SIZE + location.len as usize];
+        let result = File::open(&path).and_then(|mut f| {
+            f.seek(SeekFrom::Start(location.offset))?;
+            f.read_exact(&mut buf)
+        });
+        if let Err(e) = result {
+            warn!(segment = %path.display(), error = %e, "failed to read record");
+            return None;
+        }
+        parse_record(&buf).map(|record| record.data.to_vec())
+    }
+}
+
+impl<P: Payload> Inner<P> {
+    /// Appends a record to the active segment, rolling to a new segment
+    /// when the size limit is reached.
+    ///
+    /// A failed write is rolled back so the next append does not land after
+    /// a partial record.
+    fn append(&mut self, kind: u8, height: Height, body: &[u8]) -> Result<Location, StoreError> {
+        if self.failed {
+            return Err(StoreError::Backend(
+                "store holds a partial record and must be reopened".to_string(),
+            ));
+        }
+        if self.active_size > 0 && self.active_size >= self.config.max_segment_size {
+            self.active += 1;
+            self.writer = open_segment(&self.dir, self.active)?;
+            self.active_size = 0;
+            debug!(segment = self.active, "rolled to new segment");
+        }
+
+        let len = u32::try_from(PREFIX_SIZE + body.len())
+            .map_err(|_| StoreError::Backend("payload too large".to_string()))?;
+
+        let mut record = Vec::with_capacity(HEADER_SIZE + len as usize);
+        record.extend_from_slice(&len.to_le_bytes());
+        record.extend_from_slice(&[0; 4]);
+        record.push(kind);
+        record.extend_from_slice(&height.to_le_bytes());
+        record.extend_from_slice(body);
+        let crc = Crc32::checksum(&record[HEADER_SIZE..]);
+        record[4..HEADER_SIZE].copy_from_slice(&crc.to_le_bytes());
+
+        if let Err(e) = self.write(&record) {
+            self.rollback();
+            return Err(io_error(e));
+        }
+
+        let location = Location { segment: self.active, offset: self.active_size, len };
+        self.active_size += record.len() as u64;
+        Ok(location)
+    }
+
+    /// Returns whether pruning below `height` leaves a sealed segment with
+    /// no live payload or certificate.
+    fn frees_segment(&self, height: Height) -> bool {
+        let (pruned, live) = (self.segments(..height), self.segments(height..));
+        pruned.into_iter().any(|segment| segment < self.active && !live.contains(&segment))
+    }
+
+    /// Returns the segments holding a payload or certificate in `heights`.
+    fn segments(&self, heights: impl RangeBounds<Height> + Clone) -> BTreeSet<u64> {
+        self.by_height
+            .range(heights.clone())
+            .map(|(_, entry)| entry.location.segment)
+            .chain(self.certificates.range(heights).map(|(_, location)| location.segment))
+            .collect()
+    }
+
+    /// Deletes every sealed segment that holds no live payload or
+    /// certificate.
+    ///
+    /// Failures are logged: the records are already pruned from the indexes,
+    /// and a segment that cannot be deleted is retried on the next prune.
+    fn delete_dead_segments(&self) {
+        let live = self.segments(..);
+        let segments = match list_segments(&self.dir) {
+            Ok(segments) => segments,
+            Err(e) => {
+                warn!(dir = %self.dir.display(), error = %e, "failed to list segments");
+                return;
+            }
+        };
+        for segment in segments {
+            if segment >= self.active || live.contains(&segment) {
+                continue;
+            }
+            let path = segment_path(&self.dir, segment);
+            match fs::remove_file(&path) {
+                Ok(()) => debug!(segment = %path.display(), "deleted pruned segment"),
+                Err(e) => warn!(segment = %path.display(), error = %e, "failed to delete segment"),
+            }
+        }
+    }
+
+    /// Writes `record` to the active segment, syncing it if configured.
+    fn write(&mut self, record: &[u8]) -> io::Result<()> {
+        self.writer.write_all(record)?;
+        if self.config.sync_writes {
+            self.writer.sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Truncates the active segment back to its size before a failed write.
+    ///
+    /// Marks the store as failed if the segment cannot be truncated.
+    fn rollback(&mut self) {
+        let path = segment_path(&self.dir, self.active);
+        let result = OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_len(self.active_size).and_then(|()| file.sync_all()));
+        if let Err(e) = result {
+            warn!(segment = %path.display(), error = %e, "failed to roll back partial record");
+            self.failed = true;
+        }
+    }
+}
+
+impl<P: Payload> PayloadStore<P> for FilePayloadStore<P> {
+    async fn store(&self, payload: &P) -> Result<(), StoreError> {
+        let mut inner = self.inner.lock().expect("file store lock poisoned");
+        let height = payload.height();
+        let location = inner.append(KIND_PAYLOAD, height, &payload.encode())?;
+
+        let digest = payload.digest();
+        if let Some(previous) = inner.by_height.insert(height, Entry { location, digest }) {
+            inner.by_digest.remove(&previous.digest);
+            inner.certificates.remove(&height);
+        }
+        inner.by_digest.insert(digest, height);
+        Ok(())
+    }
+
+    async fn get(&self, digest: &P::Digest) -> Option<P> {
+        let inner = self.inner.lock().expect("file store lock poisoned");
+        let entry = *inner.by_digest.get(digest).and_then(|h| inner.by_height.get(h))?;
+        Self::read(&inner, entry.location)
+    }
+
+    async fn get_by_height(&self, height: Height) -> Option<P> {
+        let inner = self.inner.lock().expect("file store lock poisoned");
+        let entry = *inner.by_height.get(&height)?;
+        Self::read(&inner, entry.location)
+    }
+
+    async fn latest(&self) -> Option<P> {
+        let inner = self.inner.lock().expect("file store lock poisoned");
+        let (_, entry) = inner.by_height.last_key_value()?;
+        Self::read(&inner, entry.location)
+    }
+
+    async fn store_certificate(
+        &self,
+        height: Height,
+        certificate: &[u8],
+    ) -> Result<(), StoreError> {
+        let mut inner = self.inner.lock().expect("file store lock poisoned");
+        let location = inner.append(KIND_CERTIFICATE, height, certificate)?;
+        inner.certificates.insert(height, location);
+        Ok(())
+    }
+
+    async fn get_certificate(&self, height: Height) -> Option<Vec<u8>> {
+        let inner = self.inner.lock().expect("file store lock poisoned");
+        let location = *inner.certificates.get(&height)?;
+        Self::read_data(&inner, location)
+    }
+
+    async fn retained_from(&self) -> Height {
+        let inner = self.inner.lock().expect("file store lock poisoned");
+        inner.by_height.first_key_value().map_or(0, |(height, _)| *height)
+    }
+
+    async fn prune(&self, height: Height) -> Result<Vec<P>, StoreError> {
+        let mut inner = self.inner.lock().expect("file store lock poisoned");
+        // Space is only reclaimed a segment at a time, so a prune that frees
+        // no segment would just append a marker
+        if !inner.frees_segment(height) {
+            return Ok(Vec::new());
+        }
+
+        // Read the payloads before their segments can be deleted
+        let mut pruned = Vec::new();
+        for entry in inner.by_height.range(..height).map(|(_, entry)| *entry) {
+            pruned.push(Self::read(&inner, entry.location).ok_or_else(|| {
+                StoreError::Backend(format!(
+                    "failed to read payload in segment {}",
+                    entry.location.segment
+                ))
+            })?);
+        }
+
+        inner.append(KIND_PRUNE, height, &[])?;
+        let Inner { by_height, by_digest, certificates, .. } = &mut *inner;
+        prune_indexes(by_height, by_digest, certificates, height);
+        inner.delete_dead_segments();
+        debug!(height, pruned = pruned.len(), "pruned file payload store");
+        Ok(pruned)
+    }
+}
+
+/// Removes every payload and certificate below `height` from the indexes.
+fn prune_indexes<D: Eq + std::hash::Hash>(
+    by_height: &mut BTreeMap<Height, Entry<D>>,
+    by_digest: &mut HashMap<D, Height>,
+    certificates: &mut BTreeMap<Height, Location>,
+    height: Height,
+) {
+    let retained = by_height.split_off(&height);
+    for entry in std::mem::replace(by_height, retained).values() {
+        by_digest.remove(&entry.digest);
+    }
+    *certificates = certificates.split_off(&height);
+}
+
+/// A framed record borrowed from a segment.
+struct Record<'a> {
+    /// Length of the record body.
+    len: u32,
+    /// Kind of record.
+    kind: u8,
+    /// Height the record was stored at.
+    height: Height,
+    /// Encoded payload or certificate bytes.
+    data: &'a [u8],
+}
+
+/// Parses the framing of a single record from the start of `bytes`.
+///
+/// Returns `None` if the record is incomplete, fails its checksum or has an
+/// unknown kind.
+fn parse_record(bytes: &[u8]) -> Option<Record<'_>> {
+    let len = u32::from_le_bytes(bytes.get(0..4)?.try_into().ok()?);
+    let crc = u32::from_le_bytes(bytes.get(4..HEADER_SIZE)?.try_into().ok()?);
+    let body = bytes.get(HEADER_SIZE..HEADER_SIZE + len as usize)?;
+    if body.len() < PREFIX_SIZE || Crc32::checksum(body) != crc {
+        return None;
+    }
+    let kind = body[0];
+    if !matches!(kind, KIND_PAYLOAD | KIND_CERTIFICATE | KIND_PRUNE) {
+        return None;
+    }
+
+    let height = Height::from_le_bytes(body[1..PREFIX_SIZE].try_into().ok()?);
+    Some(Record { len, kind, height, data: &body[PREFIX_SIZE..] })
+}
+
+/// Returns whether an intact record starts anywhere in `bytes`.
+fn has_record(bytes: &[u8]) -> bool {
+    (0..bytes.len()).any(|offset| parse_record(&bytes[offset..]).is_some())
+}
+
+/// Returns the sorted indexes of all segments in `dir`.
+fn list_segments(dir: &Path) -> Result<Vec<u64>, StoreError> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        if let Some(index) = path.file_stem().and_then(|s| s.to_str()?.parse().ok()) {
+            segments.push(index);
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+/// Returns the path of a segment file.
+fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+    dir.join(format!("{segment:020}.{SEGMENT_EXTENSION}"))
+}
+
+/// Opens a segment for appending, creating it if needed.
+fn open_segment(dir: &Path, segment: u64) -> Result<File, StoreError> {
+    OpenOptions::new().create(true).append(true).open(segment_path(dir, segment)).map_err(io_error)
+}
+
+/// Maps an IO error into a [`StoreError`].
+fn io_error(e: io::Error) -> StoreError {
+    StoreError::Backend(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestPayload;
+
+    fn payload(height: Height) -> TestPayload {
+        TestPayload { data: vec![height as u8; 16], height, parent: None }
+    }
+
+    #[tokio::test]
+    async fn test_reopen_recovers_indexes() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = FilePayloadStore::open(dir.path()).unwrap();
+            for height in 0..5 {
+                store.store(&payload(height)).await.unwrap();
+            }
+        }
+
+        let store = FilePayloadStore::<TestPayload>::open(dir.path()).unwrap();
+        assert_eq!(store.latest().await, Some(payload(4)));
+        assert_eq!(store.get_by_height(2).await, Some(payload(2)));
+        assert_eq!(store.get(&payload(3).digest()).await, Some(payload(3)));
+        assert!(store.get_by_height(5).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reopen_recovers_certificates() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = FilePayloadStore::open(dir.path()).unwrap();
+            store.store(&payload(0)).await.unwrap();
+            store.store_certificate(0, b"certificate").await.unwrap();
+            store.store(&payload(1)).await.unwrap();
+        }
+
+        let store = FilePayloadStore::<TestPayload>::open(dir.path()).unwrap();
+        assert_eq!(store.get_certificate(0).await, Some(b"certificate".to_vec()));
+        assert!(store.get_certificate(1).await.is_none());
+        assert_eq!(store.latest().await, Some(payload(1)));
+    }
+
+    #[tokio::test]
+    async fn test_rolls_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = FileStoreConfig { max_segment_size: 64, sync_writes: false };
+        {
+            let store = FilePayloadStore::open_with_config(dir.path(), config.clone()).unwrap();
+            for height in 0..10 {
+                store.store(&payload(height)).await.unwrap();
+            }
+        }
+        assert!(list_segments(dir.path()).unwrap().len() > 1);
+
+        let store = FilePayloadStore::<TestPayload>::open_with_config(dir.path(), config).unwrap();
+        for height in 0..10 {
+            assert_eq!(store.get_by_height(height).await, Some(payload(height)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prune_deletes_dead_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = FileStoreConfig { max_segment_size: 1, sync_writes: false };
+        let store = FilePayloadStore::open_with_config(dir.path(), config.clone()).unwrap();
+        for height in 0..4 {
+            store.store(&payload(height)).await.unwrap();
+        }
+        store.store_certificate(1, b"certificate").await.unwrap();
+        assert_eq!(list_segments(dir.path()).unwrap(), vec![0, 1, 2, 3, 4]);
+
+        assert_eq!(store.prune(2).await.unwrap(), vec![payload(0), payload(1)]);
+        assert!(store.get_by_height(1).await.is_none());
+        assert!(store.get(&payload(0).digest()).await.is_none());
+        assert!(store.get_certificate(1).await.is_none());
+        assert_eq!(store.get_by_height(2).await, Some(payload(2)));
+        // Only segments holding live records or the marker remain
+        assert_eq!(list_segments(dir.path()).unwrap(), vec![2, 3, 5]);
+
+        // Pruning again is a no-op
+        assert!(store.prune(2).await.unwrap().is_empty());
+
+        drop(store);
+        let store = FilePayloadStore::<TestPayload>::open_with_config(dir.path(), config).unwrap();
+        assert!(store.get_by_height(1).await.is_none());
+        assert_eq!(store.get_by_height(2).await, Some(payload(2)));
+        assert_eq!(store.latest().await, Some(payload(3)));
+    }
+
+    #[tokio::test]
+    async fn test_reopen_applies_prune_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = FileStoreConfig { max_segment_size: 1, sync_writes: false };
+        {
+            let store = FilePayloadStore::open_with_config(dir.path(), config.clone()).unwrap();
+            for height in 0..3 {
+                store.store(&payload(height)).await.unwrap();
+            }
+            store.store_certificate(0, b"certificate").await.unwrap();
+            assert_eq!(store.prune(1).await.unwrap(), vec![payload(0)]);
+        }
+
+        // The pruned certificate shares the active segment, so only the
+        // marker keeps it from being replayed
+        let store = FilePayloadStore::<TestPayload>::open_with_config(dir.path(), config).unwrap();
+        assert!(store.get_by_height(0).await.is_none());
+        assert!(store.get_certificate(0).await.is_none());
+        assert_eq!(store.get_by_height(1).await, Some(payload(1)));
+        assert_eq!(store.retained_from().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_prune_waits_for_a_whole_segment() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FilePayloadStore::open(dir.path()).unwrap();
+        for height in 0..3 {
+            store.store(&payload(height)).await.unwrap();
+        }
+
+        // Everything shares the active segment, so nothing is pruned or
+        // written
+        let path = segment_path(dir.path(), 0);
+        let len = fs::metadata(&path).unwrap().len();
+        assert!(store.prune(2).await.unwrap().is_empty());
+        assert_eq!(fs::metadata(&path).unwrap().len(), len);
+        assert_eq!(store.get_by_height(0).await, Some(payload(0)));
+        assert_eq!(store.retained_from().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_truncates_torn_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = FilePayloadStore::open(dir.path()).unwrap();
+            store.store(&payload(0)).await.unwrap();
+            store.store(&payload(1)).await.unwrap();
+        }
+
+        // Simulate a crash halfway through appending a record.
+        let path = segment_path(dir.path(), 0);
+        let intact = fs::metadata(&path).unwrap().len();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[42, 0, 0, 0, 1, 2, 3]).unwrap();
+        drop(file);
+
+        let store = FilePayloadStore::<TestPayload>::open(dir.path()).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), intact);
+        assert_eq!(store.latest().await, Some(payload(1)));
+
+        // The log keeps working after recovery.
+        store.store(&payload(2)).await.unwrap();
+        drop(store);
+        let store = FilePayloadStore::<TestPayload>::open(dir.path()).unwrap();
+        assert_eq!(store.latest().await, Some(payload(2)));
+    }
+
+    #[tokio::test]
+    async fn test_truncates_damaged_tail() {
+        // A full-length record failing its checksum, and the zeros a crash
+        // can leave in space the file system allocated ahead of a write
+        let mut garbage = (PREFIX_SIZE as u32 + 16).to_le_bytes().to_vec();
+        garbage.extend(std::iter::repeat_n(0xab, 4 + PREFIX_SIZE + 16));
+        for tail in [garbage, vec![0; 64]] {
+            let dir = tempfile::tempdir().unwrap();
+            {
+                let store = FilePayloadStore::open(dir.path()).unwrap();
+                store.store(&payload(0)).await.unwrap();
+                store.store(&payload(1)).await.unwrap();
+            }
+
+            let path = segment_path(dir.path(), 0);
+            let intact = fs::metadata(&path).unwrap().len();
+            OpenOptions::new().append(true).open(&path).unwrap().write_all(&tail).unwrap();
+
+            let store = FilePayloadStore::<TestPayload>::open(dir.path()).unwrap();
+            assert_eq!(fs::metadata(&path).unwrap().len(), intact);
+            assert_eq!(store.latest().await, Some(payload(1)));
+
+            // The log keeps working after recovery.
+            store.store(&payload(2)).await.unwrap();
+            drop(store);
+            let store = FilePayloadStore::<TestPayload>::open(dir.path()).unwrap();
+            assert_eq!(store.latest().await, Some(payload(2)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_append_is_rolled_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FilePayloadStore::open(dir.path()).unwrap();
+        store.store(&payload(0)).await.unwrap();
+
+        // Simulate a write that fails after part of the record hit the disk.
+        let path = segment_path(dir.path(), 0);
+        let intact = fs::metadata(&path).unwrap().len();
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(&[42, 0, 0]).unwrap();
+        let writer = File::open(&path).unwrap();
+        let writable = std::mem::replace(&mut store.inner.lock().unwrap().writer, writer);
+        assert!(store.store(&payload(1)).await.is_err());
+        assert_eq!(fs::metadata(&path).unwrap().len(), intact);
+
+        // The next append lands right after the last good record.
+        store.inner.lock().unwrap().writer = writable;
+        store.store(&payload(1)).await.unwrap();
+        drop(store);
+        let store = FilePayloadStore::<TestPayload>::open(dir.path()).unwrap();
+        assert_eq!(store.get_by_height(0).await, Some(payload(0)));
+        assert_eq!(store.latest().await, Some(payload(1)));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_corrupt_sealed_segment() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = FileStoreConfig { max_segment_size: 1, sync_writes: false };
+        {
+            let store = FilePayloadStore::open_with_config(dir.path(), config.clone()).unwrap();
+            store.store(&payload(0)).await.unwrap();
+            store.store(&payload(1)).await.unwrap();
+        }
+
+        // Flip a byte inside the first (sealed) segment.
+        let path = segment_path(dir.path(), 0);
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        fs::write(&path, bytes).unwrap();
+
+        let result = FilePayloadStore::<TestPayload>::open_with_config(dir.path(), config);
+        assert!(matches!(result, Err(StoreError::Backend(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_corrupt_record_before_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let first_len;
+        {
+            let store = FilePayloadStore::open(dir.path()).unwrap();
+            store.store(&payload(0)).await.unwrap();
+            first_len = fs::metadata(segment_path(dir.path(), 0)).unwrap().len() as usize;
+            store.store(&payload(1)).await.unwrap();
+            store.store(&payload(2)).await.unwrap();
+        }
+
+        // Flip a byte inside the middle record of the active segment.
+        let path = segment_path(dir.path(), 0);
+        let mut bytes = fs::read(&path).unwrap();
+        let len = bytes.len();
+        bytes[first_len + HEADER_SIZE + PREFIX_SIZE] ^= 0xff;
+        fs::write(&path, bytes).unwrap();
+
+        let result = FilePayloadStore::<TestPayload>::open(dir.path());
+        assert!(matches!(result, Err(StoreError::Backend(_))));
+        // The records after it are kept.
+        assert_eq!(fs::metadata(&path).unwrap().len() as usize, len);
+    }
+}
@@ -0,0 +1,197 @@
+//! In-memory payload store.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use tokio::sync::RwLock;
+
+use crate::{
+    traits::{Payload, PayloadStore, StoreError},
+    types::Height,
+};
+
+/// Indexes over the stored payloads.
+struct Indexes<P: Payload> {
+    /// Certified payloads indexed by height.
+    by_height: BTreeMap<Height, P>,
+    /// Heights indexed by payload digest.
+    by_digest: HashMap<P::Digest, Height>,
+    /// Encoded certificates indexed by height.
+    certificates: BTreeMap<Height, Vec<u8>>,
+}
+
+impl<P: Payload> Default for Indexes<P> {
+    fn default() -> Self {
+        Self {
+            by_height: BTreeMap::new(),
+            by_digest: HashMap::new(),
+            certificates: BTreeMap::new(),
+        }
+    }
+}
+
+impl<P: Payload> Indexes<P> {
+    /// Inserts a payload, replacing any payload and certificate previously
+    /// stored at its height.
+    fn insert(&mut self, payload: P) {
+        let height = payload.height();
+        if let Some(previous) = self.by_height.insert(height, payload.clone()) {
+            self.by_digest.remove(&previous.digest());
+            self.certificates.remove(&height);
+        }
+        self.by_digest.insert(payload.digest(), height);
+    }
+
+    /// Removes every payload and certificate below `height`.
+    fn prune(&mut self, height: Height) -> Vec<P> {
+        let retained = self.by_height.split_off(&height);
+        let pruned = std::mem::replace(&mut self.by_height, retained);
+        self.certificates = self.certificates.split_off(&height);
+        for payload in pruned.values() {
+            self.by_digest.remove(&payload.digest());
+        }
+        pruned.into_values().collect()
+    }
+}
+
+/// A volatile [`PayloadStore`] backed by in-memory maps.
+///
+/// This is the default store used by [`PayloadAutomaton`] and [`Conductor`].
+/// All data is lost when the process exits.
+///
+/// [`PayloadAutomaton`]: crate::PayloadAutomaton
+/// [`Conductor`]: crate::Conductor
+pub struct InMemoryPayloadStore<P: Payload> {
+    indexes: Arc<RwLock<Indexes<P>>>,
+}
+
+impl<P: Payload> Clone for InMemoryPayloadStore<P> {
+    fn clone(&self) -> Self {
+        Self { indexes: Arc::clone(&self.indexes) }
+    }
+}
+
+impl<P: Payload> std::fmt::Debug for InMemoryPayloadStore<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryPayloadStore").finish_non_exhaustive()
+    }
+}
+
+impl<P: Payload> Default for InMemoryPayloadStore<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Payload> InMemoryPayloadStore<P> {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self { indexes: Arc::new(RwLock::new(Indexes::default())) }
+    }
+}
+
+impl<P: Payload> FromIterator<P> for InMemoryPayloadStore<P> {
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        let mut indexes = Indexes::default();
+        for payload in iter {
+            indexes.insert(payload);
+        }
+        Self { indexes: Arc::new(RwLock::new(indexes)) }
+    }
+}
+
+impl<P: Payload> PayloadStore<P> for InMemoryPayloadStore<P> {
+    async fn store(&self, payload: &P) -> Result<(), StoreError> {
+        self.indexes.write().await.insert(payload.clone());
+        Ok(())
+    }
+
+    async fn get(&self, digest: &P::Digest) -> Option<P> {
+        let indexes = self.indexes.read().await;
+        indexes.by_digest.get(digest).and_then(|height| indexes.by_height.get(height)).cloned()
+    }
+
+    async fn get_by_height(&self, height: Height) -> Option<P> {
+        self.indexes.read().await.by_height.get(&height).cloned()
+    }
+
+    async fn latest(&self) -> Option<P> {
+        self.indexes.read().await.by_height.last_key_value().map(|(_, p)| p.clone())
+    }
+
+    async fn store_certificate(
+        &self,
+        height: Height,
+        certificate: &[u8],
+    ) -> Result<(), StoreError> {
+        self.indexes.write().await.certificates.insert(height, certificate.to_vec());
+        Ok(())
+    }
+
+    async fn get_certificate(&self, height: Height) -> Option<Vec<u8>> {
+        self.indexes.read().await.certificates.get(&height).cloned()
+    }
+
+    async fn retained_from(&self) -> Height {
+        self.indexes.read().await.by_height.first_key_value().map_or(0, |(height, _)| *height)
+    }
+
+    async fn prune(&self, height: Height) -> Result<Vec<P>, StoreError> {
+        Ok(self.indexes.write().await.prune(height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestPayload;
+
+    #[tokio::test]
+    async fn test_store_and_lookup() {
+        let store = InMemoryPayloadStore::new();
+        assert!(store.latest().await.is_none());
+
+        let p0 = TestPayload { data: vec![1], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![2], height: 1, parent: None };
+        store.store(&p1).await.unwrap();
+        store.store(&p0).await.unwrap();
+
+        assert_eq!(store.get_by_height(0).await, Some(p0.clone()));
+        assert_eq!(store.get(&p1.digest()).await, Some(p1.clone()));
+        assert_eq!(store.latest().await, Some(p1));
+    }
+
+    #[tokio::test]
+    async fn test_replace_drops_stale_digest() {
+        let original = TestPayload { data: vec![1], height: 0, parent: None };
+        let store: InMemoryPayloadStore<_> = [original.clone()].into_iter().collect();
+
+        store.store_certificate(0, b"certificate").await.unwrap();
+        assert_eq!(store.get_certificate(0).await, Some(b"certificate".to_vec()));
+
+        let replacement = TestPayload { data: vec![2], height: 0, parent: None };
+        store.store(&replacement).await.unwrap();
+
+        assert!(store.get(&original.digest()).await.is_none());
+        assert!(store.get_certificate(0).await.is_none());
+        assert_eq!(store.get_by_height(0).await, Some(replacement));
+    }
+
+    #[tokio::test]
+    async fn test_prune() {
+        let payloads: Vec<_> = (0..4)
+            .map(|height| TestPayload { data: vec![height as u8], height, parent: None })
+            .collect();
+        let store: InMemoryPayloadStore<_> = payloads.iter().cloned().collect();
+        store.store_certificate(1, b"certificate").await.unwrap();
+
+        assert_eq!(store.prune(2).await.unwrap(), payloads[..2].to_vec());
+        assert!(store.get_by_height(1).await.is_none());
+        assert!(store.get(&payloads[0].digest()).await.is_none());
+        assert!(store.get_certificate(1).await.is_none());
+        assert_eq!(store.get_by_height(2).await, Some(payloads[2].clone()));
+        assert!(store.prune(2).await.unwrap().is_empty());
+    }
+}
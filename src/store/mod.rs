@@ -0,0 +1,16 @@
+//! [`PayloadStore`] implementations.
+//!
+//! - [`InMemoryPayloadStore`]: Volatile store used by default
+//! - [`FilePayloadStore`]: Durable append-only segment log
+//! - [`TieredPayloadStore`]: Bounded hot store in front of an archive
+//!
+//! [`PayloadStore`]: crate::PayloadStore
+
+mod file;
+pub use file::{FilePayloadStore, FileStoreConfig};
+
+mod memory;
+pub use memory::InMemoryPayloadStore;
+
+mod tiered;
+pub use tiered::TieredPayloadStore;
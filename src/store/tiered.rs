@@ -0,0 +1,121 @@
+//! Two-tier payload store.
+
+use crate::{
+    traits::{Payload, PayloadStore, StoreError},
+    types::Height,
+};
+
+/// A [`PayloadStore`] that keeps recent history in a hot store and
+/// everything in an archive.
+///
+/// Certified payloads and certificates are written to the archive first,
+/// then to the hot store. Reads are served from the hot store and fall back
+/// to the archive. Pruning only evicts from the hot store, so a
+/// [`RetentionPolicy`] bounds the hot store while evicted payloads remain
+/// available from the archive.
+///
+/// [`RetentionPolicy`]: crate::RetentionPolicy
+#[derive(Debug, Clone)]
+pub struct TieredPayloadStore<H, A> {
+    hot: H,
+    archive: A,
+}
+
+impl<H, A> TieredPayloadStore<H, A> {
+    /// Creates a store over `hot` that archives every payload to `archive`.
+    pub const fn new(hot: H, archive: A) -> Self {
+        Self { hot, archive }
+    }
+
+    /// Returns a reference to the hot store.
+    pub const fn hot(&self) -> &H {
+        &self.hot
+    }
+
+    /// Returns a reference to the archive.
+    pub const fn archive(&self) -> &A {
+        &self.archive
+    }
+}
+
+impl<P, H, A> PayloadStore<P> for TieredPayloadStore<H, A>
+where
+    P: Payload,
+    H: PayloadStore<P>,
+    A: PayloadStore<P>,
+{
+    async fn store(&self, payload: &P) -> Result<(), StoreError> {
+        self.archive.store(payload).await?;
+        self.hot.store(payload).await
+    }
+
+    async fn get(&self, digest: &P::Digest) -> Option<P> {
+        match self.hot.get(digest).await {
+            Some(payload) => Some(payload),
+            None => self.archive.get(digest).await,
+        }
+    }
+
+    async fn get_by_height(&self, height: Height) -> Option<P> {
+        match self.hot.get_by_height(height).await {
+            Some(payload) => Some(payload),
+            None => self.archive.get_by_height(height).await,
+        }
+    }
+
+    async fn latest(&self) -> Option<P> {
+        match self.hot.latest().await {
+            Some(payload) => Some(payload),
+            None => self.archive.latest().await,
+        }
+    }
+
+    async fn store_certificate(
+        &self,
+        height: Height,
+        certificate: &[u8],
+    ) -> Result<(), StoreError> {
+        self.archive.store_certificate(height, certificate).await?;
+        self.hot.store_certificate(height, certificate).await
+    }
+
+    async fn get_certificate(&self, height: Height) -> Option<Vec<u8>> {
+        match self.hot.get_certificate(height).await {
+            Some(certificate) => Some(certificate),
+            None => self.archive.get_certificate(height).await,
+        }
+    }
+
+    async fn retained_from(&self) -> Height {
+        self.archive.retained_from().await
+    }
+
+    async fn prune(&self, height: Height) -> Result<Vec<P>, StoreError> {
+        self.hot.prune(height).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{store::InMemoryPayloadStore, test_utils::TestPayload};
+
+    #[tokio::test]
+    async fn test_pruned_payloads_are_archived() {
+        let store =
+            TieredPayloadStore::new(InMemoryPayloadStore::new(), InMemoryPayloadStore::new());
+        let p0 = TestPayload { data: vec![0], height: 0, parent: None };
+        let p1 = TestPayload { data: vec![1], height: 1, parent: None };
+        store.store(&p0).await.unwrap();
+        store.store_certificate(0, b"certificate").await.unwrap();
+        store.store(&p1).await.unwrap();
+
+        assert_eq!(store.prune(1).await.unwrap(), vec![p0.clone()]);
+        assert!(store.hot().get_by_height(0).await.is_none());
+
+        assert_eq!(store.get_by_height(0).await, Some(p0.clone()));
+        assert_eq!(store.get(&p0.digest()).await, Some(p0));
+        assert_eq!(store.get_certificate(0).await, Some(b"certificate".to_vec()));
+        assert_eq!(store.latest().await, Some(p1));
+    }
+}
@@ -0,0 +1,52 @@
+//! Fixtures shared by the crate's tests.
+
+use commonware_codec::{Encode as _, ReadExt as _};
+use commonware_cryptography::{Hasher as _, sha256};
+
+use crate::{traits::Payload, types::Height};
+
+/// Payload used throughout the tests, whose digest covers its height and
+/// data.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TestPayload {
+    pub(crate) data: Vec<u8>,
+    pub(crate) height: Height,
+    pub(crate) parent: Option<sha256::Digest>,
+}
+
+impl Payload for TestPayload {
+    type Digest = sha256::Digest;
+
+    fn digest(&self) -> Self::Digest {
+        let mut hasher = sha256::Sha256::new();
+        hasher.update(&self.height.to_le_bytes());
+        hasher.update(&self.data);
+        hasher.finalize()
+    }
+
+    fn height(&self) -> Height {
+        self.height
+    }
+
+    fn parent(&self) -> Option<Self::Digest> {
+        self.parent
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&self.height.to_le_bytes());
+        buf.extend_from_slice(&self.parent.encode());
+        buf.extend_from_slice(&self.data);
+        buf
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 8 {
+            return None;
+        }
+        let height = u64::from_le_bytes(bytes[..8].try_into().ok()?);
+        let mut rest = &bytes[8..];
+        let parent = Option::<sha256::Digest>::read(&mut rest).ok()?;
+        Some(Self { data: rest.to_vec(), height, parent })
+    }
+}
@@ -5,13 +5,17 @@
 //!
 //! - [`Payload`]: Abstraction over payload types
 //! - [`EpochManager`]: Abstraction over epoch/leader management
+//! - [`PayloadStore`]: Storage for certified payloads
+//! - [`SyncSource`]: Certified history to catch up from
 
 use std::{future::Future, pin::Pin};
 
-use commonware_cryptography::Digest;
+use commonware_cryptography::{Digest, PublicKey};
 use thiserror::Error;
 
-use crate::types::{Epoch, EpochChange, Height, TransferError};
+use crate::types::{
+    Certificate, CertifiedEvent, Epoch, EpochChange, Equivocation, Height, TransferError,
+};
 
 /// Abstraction over payload types.
 ///
@@ -80,11 +84,46 @@ pub trait Payload: Clone + Send + Sync + 'static {
     fn decode(bytes: &[u8]) -> Option<Self>;
 }
 
+/// Application-level validation of payloads.
+///
+/// Runs when the sequencer commits a payload and when a validator verifies
+/// one carried by a chunk, after the height and parent digest have been
+/// checked. Use it for rules such as monotonic timestamps, size or gas
+/// limits. Validators compose in order with a [`ValidatorChain`], and any
+/// `Fn(&P, Option<&P>) -> Result<(), String>` closure is a validator.
+///
+/// [`ValidatorChain`]: crate::ValidatorChain
+pub trait PayloadValidator<P: Payload>: Send + Sync + 'static {
+    /// Validates `payload` against `parent`, the payload it extends, or
+    /// `None` for the first payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason `payload` is rejected.
+    fn validate(&self, payload: &P, parent: Option<&P>) -> Result<(), String>;
+}
+
+impl<P, F> PayloadValidator<P> for F
+where
+    P: Payload,
+    F: Fn(&P, Option<&P>) -> Result<(), String> + Send + Sync + 'static,
+{
+    fn validate(&self, payload: &P, parent: Option<&P>) -> Result<(), String> {
+        self(payload, parent)
+    }
+}
+
 /// A stream of epoch changes.
 ///
 /// This is a boxed stream to allow for different implementations.
 pub type EpochStream<K> = Pin<Box<dyn futures::Stream<Item = EpochChange<K>> + Send>>;
 
+/// A stream of certified payload events, in height order.
+pub type CertifiedStream<P> = Pin<Box<dyn futures::Stream<Item = CertifiedEvent<P>> + Send>>;
+
+/// A stream of equivocations as they are detected.
+pub type EquivocationStream<K, D> = Pin<Box<dyn futures::Stream<Item = Equivocation<K, D>> + Send>>;
+
 /// Abstraction over epoch/leader management.
 ///
 /// This trait allows pluggable leader election and epoch management
@@ -176,6 +215,47 @@ pub trait PayloadStore<P: Payload>: Clone + Send + Sync + 'static {
 
     /// Returns the latest certified payload.
     fn latest(&self) -> impl Future<Output = Option<P>> + Send;
+
+    /// Stores the encoded certificate for the payload at `height`.
+    ///
+    /// Storing a new payload at `height` discards its certificate. Stores
+    /// that do not keep certificates discard them, which is the default.
+    fn store_certificate(
+        &self,
+        height: Height,
+        certificate: &[u8],
+    ) -> impl Future<Output = Result<(), StoreError>> + Send {
+        let _ = (height, certificate);
+        async { Ok(()) }
+    }
+
+    /// Retrieves the encoded certificate for the payload at `height`.
+    ///
+    /// Returns `None` by default.
+    fn get_certificate(&self, height: Height) -> impl Future<Output = Option<Vec<u8>>> + Send {
+        let _ = height;
+        async { None }
+    }
+
+    /// Returns the oldest height the store holds a payload at, or 0 if it
+    /// holds none.
+    ///
+    /// This is where the history retained after pruning starts. Durable
+    /// stores recover it when reopened, so pruned heights stay reported as
+    /// pruned across restarts. Returns 0 by default.
+    fn retained_from(&self) -> impl Future<Output = Height> + Send {
+        async { 0 }
+    }
+
+    /// Removes every payload below `height`, along with its certificate.
+    ///
+    /// Returns the removed payloads in height order. Stores that cannot
+    /// prune keep everything and return nothing, which is the default; the
+    /// automaton only treats heights as pruned when payloads are returned.
+    fn prune(&self, height: Height) -> impl Future<Output = Result<Vec<P>, StoreError>> + Send {
+        let _ = height;
+        async { Ok(Vec::new()) }
+    }
 }
 
 /// Errors that can occur during storage operations.
@@ -190,6 +270,68 @@ pub enum StoreError {
     Backend(String),
 }
 
+/// A certified payload with its certificate, as served by a [`SyncSource`].
+///
+/// Payloads certified without a proof have no certificate.
+pub type SyncEntry<P, K> = (P, Option<Certificate<K, <P as Payload>::Digest>>);
+
+/// A source of certified history for a node that fell behind, typically a
+/// peer.
+///
+/// Entries are verified by the caller, so a source need not be trusted.
+pub trait SyncSource<P: Payload, K: PublicKey>: Send + Sync {
+    /// Returns the height of the source's latest certified payload, or
+    /// `None` if it has none.
+    fn tip(&self) -> impl Future<Output = Result<Option<Height>, SyncError>> + Send;
+
+    /// Returns the certified payloads at heights `from..=to`, in height
+    /// order, with their certificates.
+    ///
+    /// May return fewer entries than requested, but must not skip heights.
+    fn fetch(
+        &self,
+        from: Height,
+        to: Height,
+    ) -> impl Future<Output = Result<Vec<SyncEntry<P, K>>, SyncError>> + Send;
+}
+
+/// Errors that can occur while catching up from a [`SyncSource`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SyncError {
+    /// The source could not be reached or returned a malformed response.
+    #[error("sync source error: {0}")]
+    Source(String),
+
+    /// The source did not return the payload at a height below its tip.
+    #[error("sync source is missing height {height}")]
+    Incomplete {
+        /// The missing height.
+        height: Height,
+    },
+
+    /// A payload does not extend the local chain.
+    #[error("synced payload at height {height} does not extend the local chain")]
+    InvalidPayload {
+        /// The height of the payload.
+        height: Height,
+    },
+
+    /// A certificate does not match its payload or does not verify.
+    #[error("invalid certificate for height {height}")]
+    InvalidCertificate {
+        /// The height of the certified payload.
+        height: Height,
+    },
+
+    /// A payload arrived without a certificate and uncertified payloads are
+    /// not trusted.
+    #[error("missing certificate for height {height}")]
+    MissingCertificate {
+        /// The height of the payload.
+        height: Height,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -197,53 +339,14 @@ mod tests {
         sync::{Arc, RwLock},
     };
 
-    use commonware_cryptography::sha256;
     use rstest::rstest;
 
     use super::*;
-
-    // Test payload using commonware's sha256::Digest
-    #[derive(Clone, Debug, PartialEq)]
-    struct TestPayload {
-        data: Vec<u8>,
-        height: Height,
-    }
-
-    impl Payload for TestPayload {
-        type Digest = sha256::Digest;
-
-        fn digest(&self) -> Self::Digest {
-            use commonware_cryptography::Hasher as _;
-            let mut hasher = commonware_cryptography::sha256::Sha256::new();
-            hasher.update(&self.height.to_le_bytes());
-            hasher.update(&self.data);
-            hasher.finalize()
-        }
-
-        fn height(&self) -> Height {
-            self.height
-        }
-
-        fn encode(&self) -> Vec<u8> {
-            let mut buf = Vec::new();
-            buf.extend_from_slice(&self.height.to_le_bytes());
-            buf.extend_from_slice(&self.data);
-            buf
-        }
-
-        fn decode(bytes: &[u8]) -> Option<Self> {
-            if bytes.len() < 8 {
-                return None;
-            }
-            let height = u64::from_le_bytes(bytes[..8].try_into().ok()?);
-            let data = bytes[8..].to_vec();
-            Some(Self { data, height })
-        }
-    }
+    use crate::test_utils::TestPayload;
 
     #[test]
     fn payload_digest_determinism() {
-        let payload = TestPayload { data: vec![1, 2, 3, 4], height: 1 };
+        let payload = TestPayload { data: vec![1, 2, 3, 4], height: 1, parent: None };
         let d1 = payload.digest();
         let d2 = payload.digest();
         assert_eq!(d1, d2);
@@ -254,7 +357,7 @@ mod tests {
     #[case::empty_data(vec![], 0)]
     #[case::large_height(vec![255], u64::MAX)]
     fn payload_encode_decode(#[case] data: Vec<u8>, #[case] height: Height) {
-        let payload = TestPayload { data, height };
+        let payload = TestPayload { data, height, parent: None };
         let encoded = payload.encode();
         let decoded = TestPayload::decode(&encoded).unwrap();
         assert_eq!(payload, decoded);
@@ -267,15 +370,29 @@ mod tests {
         assert_eq!(format!("{error}"), expected);
     }
 
+    #[rstest]
+    #[case::source(SyncError::Source("timeout".to_string()), "sync source error: timeout")]
+    #[case::incomplete(SyncError::Incomplete { height: 3 }, "sync source is missing height 3")]
+    #[case::invalid_payload(SyncError::InvalidPayload { height: 3 }, "synced payload at height 3 does not extend the local chain")]
+    #[case::invalid_certificate(SyncError::InvalidCertificate { height: 3 }, "invalid certificate for height 3")]
+    #[case::missing_certificate(SyncError::MissingCertificate { height: 3 }, "missing certificate for height 3")]
+    fn sync_error_display(#[case] error: SyncError, #[case] expected: &str) {
+        assert_eq!(format!("{error}"), expected);
+    }
+
     // Test a simple in-memory store
     #[derive(Clone)]
     struct InMemoryStore<P: Payload> {
         payloads: Arc<RwLock<HashMap<Height, P>>>,
+        certificates: Arc<RwLock<HashMap<Height, Vec<u8>>>>,
     }
 
     impl<P: Payload> InMemoryStore<P> {
         fn new() -> Self {
-            Self { payloads: Arc::new(RwLock::new(HashMap::new())) }
+            Self {
+                payloads: Arc::new(RwLock::new(HashMap::new())),
+                certificates: Arc::new(RwLock::new(HashMap::new())),
+            }
         }
     }
 
@@ -300,13 +417,26 @@ mod tests {
             let payloads = self.payloads.read().unwrap();
             payloads.values().max_by_key(|p| p.height()).cloned()
         }
+
+        async fn store_certificate(
+            &self,
+            height: Height,
+            certificate: &[u8],
+        ) -> Result<(), StoreError> {
+            self.certificates.write().unwrap().insert(height, certificate.to_vec());
+            Ok(())
+        }
+
+        async fn get_certificate(&self, height: Height) -> Option<Vec<u8>> {
+            self.certificates.read().unwrap().get(&height).cloned()
+        }
     }
 
     #[tokio::test]
     async fn in_memory_store() {
         let store = InMemoryStore::<TestPayload>::new();
 
-        let payload = TestPayload { data: vec![1, 2, 3], height: 1 };
+        let payload = TestPayload { data: vec![1, 2, 3], height: 1, parent: None };
         store.store(&payload).await.unwrap();
 
         let retrieved = store.get_by_height(1).await.unwrap();
@@ -3,10 +3,15 @@
 //! This module contains error types, epoch-related structures, and other
 //! shared types used throughout the crate.
 
-use std::fmt;
+use std::{collections::BTreeMap, fmt, time::Duration};
 
+use bytes::{Buf, BufMut};
+use commonware_codec::{EncodeSize, Error as CodecError, RangeCfg, Read, ReadExt as _, Write};
+use commonware_cryptography::{Digest, PublicKey, Signer};
 use thiserror::Error;
 
+use crate::traits::{Payload, StoreError, SyncError};
+
 /// Epoch identifier type.
 ///
 /// Epochs are sequential periods during which a single sequencer has
@@ -19,6 +24,9 @@ pub type Epoch = u64;
 /// a chain.
 pub type Height = u64;
 
+/// Namespace used when signing and verifying [`Acknowledgment`]s.
+pub const ACK_NAMESPACE: &[u8] = b"_ARTURO_ACK";
+
 /// Epoch change notification.
 ///
 /// Emitted when the epoch transitions, notifying subscribers of the
@@ -30,9 +38,67 @@ pub struct EpochChange<K> {
     /// The public key of the sequencer for this epoch.
     pub sequencer: K,
     /// Whether the local node is the new sequencer.
+    ///
+    /// Informational only: the conductor decides leadership by comparing its
+    /// signer's public key with the epoch's sequencer.
     pub is_self: bool,
 }
 
+/// Certified payload notification.
+///
+/// Emitted to [`Conductor::subscribe_certified`] subscribers once a payload
+/// is certified and stored.
+///
+/// [`Conductor::subscribe_certified`]: crate::Conductor::subscribe_certified
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertifiedPayload<P> {
+    /// The certified payload.
+    pub payload: P,
+    /// Height of the payload.
+    pub height: Height,
+    /// Epoch the payload was certified in.
+    pub epoch: Epoch,
+}
+
+/// A committed payload's certification, resolved by a [`CommitReceipt`].
+///
+/// [`CommitReceipt`]: crate::CommitReceipt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Committed<D> {
+    /// Height of the certified payload.
+    pub height: Height,
+    /// Digest of the certified payload.
+    pub digest: D,
+    /// Epoch the payload was certified in.
+    pub epoch: Epoch,
+}
+
+/// Snapshot of the proposal queue, for throttling payload production.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueStatus {
+    /// Number of committed payloads awaiting certification.
+    pub depth: usize,
+    /// Maximum number of payloads that may await certification at once.
+    pub capacity: usize,
+    /// How long the oldest payload awaiting certification has waited, or
+    /// `None` if the queue is empty.
+    pub oldest_pending_age: Option<Duration>,
+}
+
+/// Event delivered to certified payload subscribers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertifiedEvent<P> {
+    /// A payload was certified.
+    Certified(CertifiedPayload<P>),
+    /// The subscriber fell behind and missed this many certified payloads.
+    ///
+    /// Delivery resumes with the oldest payload still buffered.
+    Lagged {
+        /// Number of certified payloads skipped.
+        missed: u64,
+    },
+}
+
 impl<K: fmt::Display> fmt::Display for EpochChange<K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -43,6 +109,39 @@ impl<K: fmt::Display> fmt::Display for EpochChange<K> {
     }
 }
 
+/// How much certified history is kept in a [`PayloadStore`].
+///
+/// The latest certified payload is always retained, so the chain can still
+/// be extended after pruning.
+///
+/// [`PayloadStore`]: crate::PayloadStore
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RetentionPolicy {
+    /// Keep every certified payload.
+    #[default]
+    KeepAll,
+    /// Keep the given number of most recent heights.
+    KeepLast(u64),
+    /// Keep payloads certified within the given window.
+    ///
+    /// Payloads already in the store when the node started are treated as
+    /// older than any payload it certified itself.
+    KeepFor(Duration),
+}
+
+/// What [`Conductor::commit`] does when the proposal queue is full.
+///
+/// [`Conductor::commit`]: crate::Conductor::commit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// Fail with [`ConductorError::Backpressure`] right away.
+    #[default]
+    Reject,
+    /// Wait up to the given time for a queued payload to be certified
+    /// before failing with [`ConductorError::Backpressure`].
+    Wait(Duration),
+}
+
 /// Errors that can occur during conductor operations.
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum ConductorError {
@@ -66,12 +165,96 @@ pub enum ConductorError {
     /// The payload's parent digest does not match.
     #[error("parent mismatch: expected {expected}, got {got}")]
     ParentMismatch {
-        /// The expected parent digest.
+        /// The expected parent digest, hex encoded, or `none`.
         expected: String,
-        /// The actual parent digest received.
+        /// The actual parent digest received, hex encoded, or `none`.
         got: String,
     },
 
+    /// Too many proposals are awaiting certification.
+    ///
+    /// The [`PayloadAutomaton`] returns this whenever its window is full.
+    /// The [`Conductor`] reports a full window as the retryable
+    /// [`ConductorError::Backpressure`] instead, and only returns this for a
+    /// batch larger than the window: such a batch can never be accepted, so
+    /// retrying it cannot succeed.
+    ///
+    /// [`PayloadAutomaton`]: crate::PayloadAutomaton
+    /// [`Conductor`]: crate::Conductor
+    #[error("proposal window full: {limit} payloads awaiting certification")]
+    WindowFull {
+        /// The maximum number of in-flight proposals.
+        limit: usize,
+    },
+
+    /// The proposal queue is full; retry once queued payloads are
+    /// certified.
+    ///
+    /// Unlike [`ConductorError::WindowFull`], this is transient.
+    #[error("proposal queue full: {depth} payloads awaiting certification")]
+    Backpressure {
+        /// Number of payloads awaiting certification.
+        depth: usize,
+    },
+
+    /// An acknowledgment came from a key outside the epoch's validator set.
+    #[error("unknown validator for epoch {epoch}")]
+    UnknownValidator {
+        /// The epoch the acknowledgment was signed in.
+        epoch: Epoch,
+    },
+
+    /// An acknowledgment's signature did not verify.
+    #[error("invalid acknowledgment signature")]
+    InvalidSignature,
+
+    /// A snapshot could not be restored.
+    #[error("invalid snapshot: {0}")]
+    InvalidSnapshot(String),
+
+    /// The requested height was pruned by the [`RetentionPolicy`].
+    #[error("height {height} was pruned: oldest retained height is {retained_from}")]
+    Pruned {
+        /// The requested height.
+        height: Height,
+        /// The oldest height still retained.
+        retained_from: Height,
+    },
+
+    /// A proposal or acknowledgment belongs to an epoch that has been fenced
+    /// off.
+    #[error("stale epoch {epoch}: current epoch is {current}")]
+    StaleEpoch {
+        /// The epoch of the proposal or acknowledgment.
+        epoch: Epoch,
+        /// The current epoch.
+        current: Epoch,
+    },
+
+    /// A committed payload was not certified before the commit timeout.
+    #[error("payload at height {height} was not certified in time")]
+    CommitTimeout {
+        /// Height of the committed payload.
+        height: Height,
+    },
+
+    /// A committed payload was dropped by an epoch change before it was
+    /// certified.
+    #[error("payload at height {height} was dropped by the change to epoch {epoch}")]
+    EpochChanged {
+        /// Height of the committed payload.
+        height: Height,
+        /// The epoch that dropped it.
+        epoch: Epoch,
+    },
+
+    /// A different payload was certified at a committed payload's height.
+    #[error("payload at height {height} was superseded")]
+    Superseded {
+        /// Height of the committed payload.
+        height: Height,
+    },
+
     /// The conductor is not yet initialized.
     #[error("conductor not initialized")]
     NotInitialized,
@@ -79,6 +262,66 @@ pub enum ConductorError {
     /// Internal channel was closed unexpectedly.
     #[error("internal channel closed")]
     ChannelClosed,
+
+    /// The payload store failed.
+    #[error(transparent)]
+    Store(#[from] StoreError),
+
+    /// The node is catching up and cannot take part yet.
+    #[error("node is syncing")]
+    Syncing,
+
+    /// Catching up from a sync source failed.
+    #[error(transparent)]
+    Sync(#[from] SyncError),
+}
+
+impl From<ValidationError> for ConductorError {
+    fn from(error: ValidationError) -> Self {
+        match error {
+            ValidationError::InvalidHeight { expected, got } => {
+                Self::InvalidHeight { expected, got }
+            }
+            ValidationError::ParentMismatch { expected, got } => {
+                Self::ParentMismatch { expected, got }
+            }
+            ValidationError::Rejected(reason) => Self::ValidationFailed(reason),
+        }
+    }
+}
+
+/// Reasons a payload fails [`PayloadAutomaton::validate`].
+///
+/// Each variant maps onto the [`ConductorError`] of the same name, with
+/// [`ValidationError::Rejected`] mapping onto
+/// [`ConductorError::ValidationFailed`].
+///
+/// [`PayloadAutomaton::validate`]: crate::PayloadAutomaton::validate
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    /// The payload height does not follow the newest payload.
+    #[error("invalid height: expected {expected}, got {got}")]
+    InvalidHeight {
+        /// The expected height.
+        expected: Height,
+        /// The payload's height.
+        got: Height,
+    },
+
+    /// The payload's parent is not the newest payload.
+    #[error("parent mismatch: expected {expected}, got {got}")]
+    ParentMismatch {
+        /// Digest of the newest payload, hex encoded, or `none`.
+        expected: String,
+        /// The payload's parent digest, hex encoded.
+        got: String,
+    },
+
+    /// The [`PayloadValidator`] rejected the payload.
+    ///
+    /// [`PayloadValidator`]: crate::PayloadValidator
+    #[error("payload validation failed: {0}")]
+    Rejected(String),
 }
 
 /// Errors that can occur during leader transfer.
@@ -105,56 +348,507 @@ pub enum TransferError {
     Timeout,
 }
 
+/// A validator's signed acknowledgment of a pending payload.
+///
+/// The signature covers the epoch, height and digest under
+/// [`ACK_NAMESPACE`], so an acknowledgment cannot be replayed for another
+/// payload, height or epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Acknowledgment<K: PublicKey, D: Digest> {
+    /// The validator that signed the acknowledgment.
+    pub validator: K,
+    /// The epoch the acknowledgment was signed in.
+    pub epoch: Epoch,
+    /// The height of the acknowledged payload.
+    pub height: Height,
+    /// The digest of the acknowledged payload.
+    pub digest: D,
+    /// The validator's signature over the epoch, height and digest.
+    pub signature: K::Signature,
+}
+
+impl<K: PublicKey, D: Digest> Acknowledgment<K, D> {
+    /// Signs an acknowledgment of `digest` at `height` in `epoch`.
+    pub fn sign<S>(signer: &S, epoch: Epoch, height: Height, digest: D) -> Self
+    where
+        S: Signer<PublicKey = K, Signature = K::Signature>,
+    {
+        let signature = signer.sign(ACK_NAMESPACE, &ack_message(epoch, height, &digest));
+        Self { validator: signer.public_key(), epoch, height, digest, signature }
+    }
+
+    /// Returns true if the signature was produced by `validator`.
+    pub fn verify(&self) -> bool {
+        self.validator.verify(
+            ACK_NAMESPACE,
+            &ack_message(self.epoch, self.height, &self.digest),
+            &self.signature,
+        )
+    }
+}
+
+/// Evidence that a validator or sequencer committed to two different
+/// digests at the same epoch and height.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Equivocation<K: PublicKey, D: Digest> {
+    /// A validator signed acknowledgments for two different digests.
+    ///
+    /// Both acknowledgments carry their signatures, so anyone can check the
+    /// evidence with [`Equivocation::verify`] without trusting the node that
+    /// reported it.
+    Validator {
+        /// The acknowledgment seen first.
+        first: Acknowledgment<K, D>,
+        /// The conflicting acknowledgment.
+        second: Acknowledgment<K, D>,
+    },
+    /// A sequencer proposed two different payloads.
+    ///
+    /// Observed from the payloads the sequencer relayed. It carries no
+    /// signatures, so it is only as trustworthy as the node that reported
+    /// it.
+    Sequencer {
+        /// The sequencer that proposed both payloads.
+        sequencer: K,
+        /// Epoch both payloads were proposed in.
+        epoch: Epoch,
+        /// Height of both payloads.
+        height: Height,
+        /// Digest of the payload seen first.
+        first: D,
+        /// Digest of the conflicting payload.
+        second: D,
+    },
+}
+
+impl<K: PublicKey, D: Digest> Equivocation<K, D> {
+    /// Returns the validator or sequencer that equivocated.
+    pub const fn offender(&self) -> &K {
+        match self {
+            Self::Validator { first, .. } => &first.validator,
+            Self::Sequencer { sequencer, .. } => sequencer,
+        }
+    }
+
+    /// Returns the epoch of the conflicting digests.
+    pub const fn epoch(&self) -> Epoch {
+        match self {
+            Self::Validator { first, .. } => first.epoch,
+            Self::Sequencer { epoch, .. } => *epoch,
+        }
+    }
+
+    /// Returns the height of the conflicting digests.
+    pub const fn height(&self) -> Height {
+        match self {
+            Self::Validator { first, .. } => first.height,
+            Self::Sequencer { height, .. } => *height,
+        }
+    }
+
+    /// Returns true if the evidence can be checked on its own: both
+    /// acknowledgments are from the same validator at the same epoch and
+    /// height, carry different digests, and verify.
+    ///
+    /// Sequencer evidence is unsigned and never verifies.
+    pub fn verify(&self) -> bool {
+        match self {
+            Self::Validator { first, second } => {
+                first.validator == second.validator
+                    && first.epoch == second.epoch
+                    && first.height == second.height
+                    && first.digest != second.digest
+                    && first.verify()
+                    && second.verify()
+            }
+            Self::Sequencer { .. } => false,
+        }
+    }
+}
+
+/// Returns the message signed by an acknowledgment.
+fn ack_message<D: Digest>(epoch: Epoch, height: Height, digest: &D) -> Vec<u8> {
+    let mut message = Vec::with_capacity(16 + digest.as_ref().len());
+    message.extend_from_slice(&epoch.to_le_bytes());
+    message.extend_from_slice(&height.to_le_bytes());
+    message.extend_from_slice(digest.as_ref());
+    message
+}
+
+/// Upper bound on the encoded size of an engine chunk certificate per
+/// validator: a signature of any commonware scheme plus its signer index.
+const MAX_CHUNK_CERTIFICATE_BYTES_PER_VALIDATOR: usize = 128;
+
+/// Proof that a payload was certified by a quorum of validators.
+///
+/// Carries the acknowledgment signatures that certified the payload, so
+/// anyone who knows the epoch's validator set can check it with
+/// [`Certificate::verify`] instead of trusting the node that served it.
+/// Payloads certified by the ordered_broadcast engine carry the engine's
+/// quorum certificate over their chunk instead, see [`ChunkProof`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Certificate<K: PublicKey, D: Digest> {
+    /// The epoch the payload was certified in.
+    pub epoch: Epoch,
+    /// The height of the certified payload.
+    pub height: Height,
+    /// The digest of the certified payload.
+    pub digest: D,
+    /// Acknowledgment signatures, keyed by the validator that signed.
+    ///
+    /// Empty when the certificate is proven by `chunk`.
+    pub signatures: BTreeMap<K, K::Signature>,
+    /// The engine's chunk certificate, if the payload was certified by the
+    /// ordered_broadcast engine.
+    pub chunk: Option<ChunkProof<K>>,
+}
+
+impl<K: PublicKey, D: Digest> Certificate<K, D> {
+    /// Builds a certificate proven by an engine chunk certificate.
+    pub const fn from_chunk(epoch: Epoch, height: Height, digest: D, chunk: ChunkProof<K>) -> Self {
+        Self { epoch, height, digest, signatures: BTreeMap::new(), chunk: Some(chunk) }
+    }
+
+    /// Returns true if at least `threshold` members of `validators` signed
+    /// the certificate and every signature verifies.
+    ///
+    /// A certificate proven by a [`ChunkProof`] carries no acknowledgment
+    /// signatures and never verifies here; it is checked against the
+    /// engine's signing scheme instead.
+    pub fn verify(&self, validators: &[K], threshold: usize) -> bool {
+        if self.chunk.is_some() || self.signatures.len() < threshold {
+            return false;
+        }
+        let message = ack_message(self.epoch, self.height, &self.digest);
+        self.signatures.iter().all(|(validator, signature)| {
+            validators.contains(validator) && validator.verify(ACK_NAMESPACE, &message, signature)
+        })
+    }
+}
+
+impl<K: PublicKey, D: Digest> Write for Certificate<K, D> {
+    fn write(&self, buf: &mut impl BufMut) {
+        self.epoch.write(buf);
+        self.height.write(buf);
+        self.digest.write(buf);
+        self.signatures.write(buf);
+        self.chunk.write(buf);
+    }
+}
+
+impl<K: PublicKey, D: Digest> EncodeSize for Certificate<K, D> {
+    fn encode_size(&self) -> usize {
+        self.epoch.encode_size()
+            + self.height.encode_size()
+            + self.digest.encode_size()
+            + self.signatures.encode_size()
+            + self.chunk.encode_size()
+    }
+}
+
+/// Decodes a certificate holding at most `max_signatures` signatures,
+/// typically the size of the epoch's validator set.
+impl<K: PublicKey, D: Digest> Read for Certificate<K, D> {
+    type Cfg = usize;
+
+    fn read_cfg(buf: &mut impl Buf, max_signatures: &usize) -> Result<Self, CodecError> {
+        let epoch = Epoch::read(buf)?;
+        let height = Height::read(buf)?;
+        let digest = D::read(buf)?;
+        let signatures = BTreeMap::read_cfg(buf, &(RangeCfg::from(..=*max_signatures), ((), ())))?;
+        let chunk = Option::<ChunkProof<K>>::read_cfg(buf, max_signatures)?;
+        Ok(Self { epoch, height, digest, signatures, chunk })
+    }
+}
+
+/// The ordered_broadcast engine's proof that a quorum acknowledged the
+/// chunk carrying a payload.
+///
+/// The engine signs chunks rather than payloads, so the proof names the
+/// chunk by its sequencer and its height in the sequencer's chain; the
+/// chunk's payload is the certificate's digest. The quorum certificate is
+/// kept encoded because its format depends on the engine's signing scheme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkProof<K: PublicKey> {
+    /// The sequencer that broadcast the chunk.
+    pub sequencer: K,
+    /// The chunk's height in the sequencer's chain.
+    pub height: Height,
+    /// The engine's encoded quorum certificate over the chunk.
+    pub certificate: Vec<u8>,
+}
+
+impl<K: PublicKey> Write for ChunkProof<K> {
+    fn write(&self, buf: &mut impl BufMut) {
+        self.sequencer.write(buf);
+        self.height.write(buf);
+        self.certificate.write(buf);
+    }
+}
+
+impl<K: PublicKey> EncodeSize for ChunkProof<K> {
+    fn encode_size(&self) -> usize {
+        self.sequencer.encode_size() + self.height.encode_size() + self.certificate.encode_size()
+    }
+}
+
+/// Decodes a proof whose certificate is signed by at most `max_signatures`
+/// validators.
+impl<K: PublicKey> Read for ChunkProof<K> {
+    type Cfg = usize;
+
+    fn read_cfg(buf: &mut impl Buf, max_signatures: &usize) -> Result<Self, CodecError> {
+        let sequencer = K::read(buf)?;
+        let height = Height::read(buf)?;
+        let max_len = max_signatures.saturating_mul(MAX_CHUNK_CERTIFICATE_BYTES_PER_VALIDATOR);
+        let certificate = Vec::<u8>::read_cfg(buf, &(RangeCfg::from(..=max_len), ()))?;
+        Ok(Self { sequencer, height, certificate })
+    }
+}
+
+/// A self-contained copy of an automaton's certified chain.
+///
+/// Produced by [`PayloadAutomaton::snapshot`] and loaded with
+/// [`PayloadAutomaton::restore`] so a new or rebuilt node can start from a
+/// trusted point instead of height 0. Payloads are serialized with
+/// [`Payload::encode`].
+///
+/// [`PayloadAutomaton::snapshot`]: crate::PayloadAutomaton::snapshot
+/// [`PayloadAutomaton::restore`]: crate::PayloadAutomaton::restore
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<P: Payload, K: PublicKey> {
+    /// The epoch the snapshot was taken in.
+    pub epoch: Epoch,
+    /// The retained certified history, in height order, ending with the
+    /// latest certified payload.
+    pub history: Vec<P>,
+    /// The certificate of the latest certified payload, if it has one.
+    pub certificate: Option<Certificate<K, P::Digest>>,
+}
+
+impl<P: Payload, K: PublicKey> Snapshot<P, K> {
+    /// Returns the latest certified payload in the snapshot.
+    pub fn latest(&self) -> Option<&P> {
+        self.history.last()
+    }
+}
+
+impl<P: Payload, K: PublicKey> Write for Snapshot<P, K> {
+    fn write(&self, buf: &mut impl BufMut) {
+        self.epoch.write(buf);
+        let history: Vec<Vec<u8>> = self.history.iter().map(Payload::encode).collect();
+        history.write(buf);
+        self.certificate.write(buf);
+    }
+}
+
+impl<P: Payload, K: PublicKey> EncodeSize for Snapshot<P, K> {
+    fn encode_size(&self) -> usize {
+        let history: Vec<Vec<u8>> = self.history.iter().map(Payload::encode).collect();
+        self.epoch.encode_size() + history.encode_size() + self.certificate.encode_size()
+    }
+}
+
+/// Decodes a snapshot whose certificate holds at most `max_signatures`
+/// signatures, typically the size of the validator set.
+impl<P: Payload, K: PublicKey> Read for Snapshot<P, K> {
+    type Cfg = usize;
+
+    fn read_cfg(buf: &mut impl Buf, max_signatures: &usize) -> Result<Self, CodecError> {
+        let epoch = Epoch::read(buf)?;
+        let history =
+            Vec::<Vec<u8>>::read_cfg(buf, &(RangeCfg::from(..), (RangeCfg::from(..), ())))?
+                .iter()
+                .map(|bytes| {
+                    P::decode(bytes).ok_or(CodecError::Invalid("Snapshot", "invalid payload"))
+                })
+                .collect::<Result<_, _>>()?;
+        let certificate = Option::<Certificate<K, P::Digest>>::read_cfg(buf, max_signatures)?;
+        Ok(Self { epoch, history, certificate })
+    }
+}
+
 /// State of a pending payload awaiting certification.
 #[derive(Debug, Clone)]
-pub struct PendingPayload<P> {
+pub struct PendingPayload<P, K: PublicKey> {
     /// The payload awaiting certification.
     pub payload: P,
-    /// Number of acknowledgments received.
-    pub acks: usize,
+    /// The epoch the payload was proposed in.
+    pub epoch: Epoch,
+    /// Signatures received, keyed by the validator that signed.
+    pub signatures: BTreeMap<K, K::Signature>,
     /// Required acknowledgments for certification.
     pub threshold: usize,
 }
 
-impl<P> PendingPayload<P> {
-    /// Creates a new pending payload.
-    pub const fn new(payload: P, threshold: usize) -> Self {
-        Self { payload, acks: 0, threshold }
+impl<P, K: PublicKey> PendingPayload<P, K> {
+    /// Creates a new pending payload proposed in `epoch`.
+    pub const fn new(payload: P, epoch: Epoch, threshold: usize) -> Self {
+        Self { payload, epoch, signatures: BTreeMap::new(), threshold }
+    }
+
+    /// Returns the number of distinct validators that have acknowledged.
+    pub fn acks(&self) -> usize {
+        self.signatures.len()
     }
 
     /// Returns true if the payload has reached quorum.
-    pub const fn is_certified(&self) -> bool {
-        self.acks >= self.threshold
+    pub fn is_certified(&self) -> bool {
+        self.acks() >= self.threshold
+    }
+
+    /// Returns true if `validator` has acknowledged the payload.
+    pub fn has_signed(&self, validator: &K) -> bool {
+        self.signatures.contains_key(validator)
+    }
+
+    /// Records an acknowledgment from `validator`.
+    ///
+    /// Returns false, leaving the existing signature in place, if the
+    /// validator has already acknowledged.
+    pub fn acknowledge(&mut self, validator: K, signature: K::Signature) -> bool {
+        if self.has_signed(&validator) {
+            return false;
+        }
+        self.signatures.insert(validator, signature);
+        true
     }
+}
 
-    /// Records an acknowledgment.
-    pub const fn acknowledge(&mut self) {
-        self.acks += 1;
+impl<P: Payload, K: PublicKey> PendingPayload<P, K> {
+    /// Builds a certificate from the signatures collected so far.
+    pub fn certificate(&self) -> Certificate<K, P::Digest> {
+        Certificate {
+            epoch: self.epoch,
+            height: self.payload.height(),
+            digest: self.payload.digest(),
+            signatures: self.signatures.clone(),
+            chunk: None,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use commonware_codec::{Decode as _, Encode as _};
+    use commonware_cryptography::{Hasher as _, ed25519, sha256};
     use rstest::rstest;
 
     use super::*;
 
+    fn digest(data: &[u8]) -> sha256::Digest {
+        let mut hasher = sha256::Sha256::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
     #[rstest]
     #[case::below_threshold(2, 3, false)]
     #[case::at_threshold(3, 3, true)]
     #[case::above_threshold(4, 3, true)]
     fn pending_payload_certification(
-        #[case] acks: usize,
+        #[case] acks: u64,
         #[case] threshold: usize,
         #[case] expected_certified: bool,
     ) {
-        let mut pending = PendingPayload::new("test", threshold);
-        for _ in 0..acks {
-            pending.acknowledge();
+        let mut pending = PendingPayload::new("test", 0, threshold);
+        for seed in 0..acks {
+            let ack =
+                Acknowledgment::sign(&ed25519::PrivateKey::from_seed(seed), 0, 0, digest(b"test"));
+            pending.acknowledge(ack.validator, ack.signature);
         }
         assert_eq!(pending.is_certified(), expected_certified);
     }
 
+    #[test]
+    fn pending_payload_ignores_duplicate_signer() {
+        let signer = ed25519::PrivateKey::from_seed(1);
+        let ack = Acknowledgment::sign(&signer, 0, 0, digest(b"test"));
+
+        let mut pending = PendingPayload::new("test", 0, 2);
+        assert!(pending.acknowledge(ack.validator.clone(), ack.signature.clone()));
+        assert!(!pending.acknowledge(ack.validator.clone(), ack.signature));
+        assert_eq!(pending.acks(), 1);
+        assert!(pending.has_signed(&ack.validator));
+        assert!(!pending.is_certified());
+    }
+
+    #[test]
+    fn acknowledgment_verify() {
+        let signer = ed25519::PrivateKey::from_seed(1);
+        let ack = Acknowledgment::sign(&signer, 3, 7, digest(b"payload"));
+        assert!(ack.verify());
+
+        // Tampering with any signed field invalidates the signature
+        assert!(!Acknowledgment { epoch: 4, ..ack.clone() }.verify());
+        assert!(!Acknowledgment { height: 8, ..ack.clone() }.verify());
+        assert!(!Acknowledgment { digest: digest(b"other"), ..ack.clone() }.verify());
+        let other = ed25519::PrivateKey::from_seed(2).public_key();
+        assert!(!Acknowledgment { validator: other, ..ack }.verify());
+    }
+
+    #[test]
+    fn equivocation_verify() {
+        let signer = ed25519::PrivateKey::from_seed(1);
+        let first = Acknowledgment::sign(&signer, 2, 5, digest(b"first"));
+        let second = Acknowledgment::sign(&signer, 2, 5, digest(b"second"));
+        let equivocation = Equivocation::Validator { first: first.clone(), second: second.clone() };
+        assert!(equivocation.verify());
+        assert_eq!(equivocation.offender(), &signer.public_key());
+        assert_eq!((equivocation.epoch(), equivocation.height()), (2, 5));
+
+        // The same digest twice is not a conflict
+        assert!(!Equivocation::Validator { first: first.clone(), second: first.clone() }.verify());
+
+        // Neither is a different height
+        let later = Acknowledgment::sign(&signer, 2, 6, digest(b"second"));
+        assert!(!Equivocation::Validator { first: first.clone(), second: later }.verify());
+
+        // Both signatures must verify
+        let mut forged = second;
+        forged.signature = first.signature.clone();
+        assert!(!Equivocation::Validator { first, second: forged }.verify());
+
+        // Sequencer evidence carries no signatures
+        let equivocation = Equivocation::Sequencer {
+            sequencer: signer.public_key(),
+            epoch: 2,
+            height: 5,
+            first: digest(b"first"),
+            second: digest(b"second"),
+        };
+        assert!(!equivocation.verify());
+        assert_eq!(equivocation.offender(), &signer.public_key());
+    }
+
+    #[test]
+    fn certificate_verify_and_roundtrip() {
+        let validators: Vec<_> =
+            (1..=3).map(|seed| ed25519::PrivateKey::from_seed(seed).public_key()).collect();
+        let mut signatures = BTreeMap::new();
+        for seed in 1..=2 {
+            let ack =
+                Acknowledgment::sign(&ed25519::PrivateKey::from_seed(seed), 3, 7, digest(b"p"));
+            signatures.insert(ack.validator, ack.signature);
+        }
+        let certificate =
+            Certificate { epoch: 3, height: 7, digest: digest(b"p"), signatures, chunk: None };
+
+        assert!(certificate.verify(&validators, 2));
+        assert!(!certificate.verify(&validators, 3));
+        assert!(!certificate.verify(&validators[1..], 2));
+        assert!(!Certificate { height: 8, ..certificate.clone() }.verify(&validators, 2));
+
+        let decoded = Certificate::decode_cfg(certificate.encode(), &3).unwrap();
+        assert_eq!(decoded, certificate);
+
+        // More signatures than there are validators is rejected
+        assert!(
+            Certificate::<ed25519::PublicKey, sha256::Digest>::decode_cfg(certificate.encode(), &1)
+                .is_err()
+        );
+    }
+
     #[test]
     fn epoch_change_display() {
         let change = EpochChange { epoch: 42, sequencer: "node1", is_self: true };
@@ -169,10 +863,39 @@ mod tests {
     #[case::not_initialized(ConductorError::NotInitialized, "conductor not initialized")]
     #[case::channel_closed(ConductorError::ChannelClosed, "internal channel closed")]
     #[case::validation_failed(ConductorError::ValidationFailed("bad".to_string()), "payload validation failed: bad")]
+    #[case::unknown_validator(ConductorError::UnknownValidator { epoch: 2 }, "unknown validator for epoch 2")]
+    #[case::invalid_signature(ConductorError::InvalidSignature, "invalid acknowledgment signature")]
+    #[case::invalid_snapshot(ConductorError::InvalidSnapshot("empty".to_string()), "invalid snapshot: empty")]
+    #[case::pruned(ConductorError::Pruned { height: 1, retained_from: 5 }, "height 1 was pruned: oldest retained height is 5")]
+    #[case::stale_epoch(ConductorError::StaleEpoch { epoch: 1, current: 3 }, "stale epoch 1: current epoch is 3")]
+    #[case::window_full(ConductorError::WindowFull { limit: 4 }, "proposal window full: 4 payloads awaiting certification")]
+    #[case::backpressure(ConductorError::Backpressure { depth: 4 }, "proposal queue full: 4 payloads awaiting certification")]
+    #[case::commit_timeout(ConductorError::CommitTimeout { height: 4 }, "payload at height 4 was not certified in time")]
+    #[case::epoch_changed(ConductorError::EpochChanged { height: 4, epoch: 2 }, "payload at height 4 was dropped by the change to epoch 2")]
+    #[case::superseded(ConductorError::Superseded { height: 4 }, "payload at height 4 was superseded")]
+    #[case::syncing(ConductorError::Syncing, "node is syncing")]
+    #[case::sync(ConductorError::Sync(SyncError::Incomplete { height: 2 }), "sync source is missing height 2")]
+    #[case::store(ConductorError::Store(StoreError::Backend("disk".to_string())), "storage error: disk")]
     fn conductor_error_display(#[case] error: ConductorError, #[case] expected: &str) {
         assert_eq!(format!("{error}"), expected);
     }
 
+    #[rstest]
+    #[case::invalid_height(ValidationError::InvalidHeight { expected: 1, got: 5 }, ConductorError::InvalidHeight { expected: 1, got: 5 })]
+    #[case::parent_mismatch(
+        ValidationError::ParentMismatch { expected: "aa".to_string(), got: "bb".to_string() },
+        ConductorError::ParentMismatch { expected: "aa".to_string(), got: "bb".to_string() }
+    )]
+    #[case::rejected(ValidationError::Rejected("too large".to_string()), ConductorError::ValidationFailed("too large".to_string()))]
+    fn validation_error_maps_onto_conductor_error(
+        #[case] error: ValidationError,
+        #[case] expected: ConductorError,
+    ) {
+        // Both render the same message
+        assert_eq!(error.to_string(), expected.to_string());
+        assert_eq!(ConductorError::from(error), expected);
+    }
+
     #[rstest]
     #[case::not_supported(TransferError::NotSupported, "transfer not supported")]
     #[case::no_successor(TransferError::NoSuccessor, "no successor available")]
@@ -0,0 +1,127 @@
+//! Composable payload validators.
+
+use std::{fmt, sync::Arc};
+
+use crate::traits::{Payload, PayloadValidator};
+
+/// A [`PayloadValidator`] that runs validators in order.
+///
+/// A payload is accepted if every validator accepts it, and rejected with
+/// the reason of the first validator that rejects it. An empty chain
+/// accepts every payload.
+///
+/// # Example
+///
+/// ```ignore
+/// use arturo::ValidatorChain;
+///
+/// let validator = ValidatorChain::new()
+///     .with(|payload: &MyPayload, parent: Option<&MyPayload>| {
+///         match parent {
+///             Some(parent) if payload.timestamp < parent.timestamp => {
+///                 Err("timestamp went backwards".to_string())
+///             }
+///             _ => Ok(()),
+///         }
+///     });
+/// ```
+pub struct ValidatorChain<P> {
+    validators: Vec<Arc<dyn PayloadValidator<P>>>,
+}
+
+impl<P: Payload> ValidatorChain<P> {
+    /// Creates an empty chain.
+    pub const fn new() -> Self {
+        Self { validators: Vec::new() }
+    }
+
+    /// Appends `validator` to the chain.
+    pub fn with(mut self, validator: impl PayloadValidator<P>) -> Self {
+        self.validators.push(Arc::new(validator));
+        self
+    }
+
+    /// Returns the number of validators in the chain.
+    pub fn len(&self) -> usize {
+        self.validators.len()
+    }
+
+    /// Returns true if the chain has no validators.
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+}
+
+impl<P: Payload> Default for ValidatorChain<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Clone for ValidatorChain<P> {
+    fn clone(&self) -> Self {
+        Self { validators: self.validators.clone() }
+    }
+}
+
+impl<P> fmt::Debug for ValidatorChain<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidatorChain").field("validators", &self.validators.len()).finish()
+    }
+}
+
+impl<P: Payload> PayloadValidator<P> for ValidatorChain<P> {
+    fn validate(&self, payload: &P, parent: Option<&P>) -> Result<(), String> {
+        self.validators.iter().try_for_each(|validator| validator.validate(payload, parent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestPayload;
+
+    // Payload whose only data byte is a timestamp
+    fn stamped(timestamp: u8, height: u64) -> TestPayload {
+        TestPayload { data: vec![timestamp], height, parent: None }
+    }
+
+    fn monotonic(payload: &TestPayload, parent: Option<&TestPayload>) -> Result<(), String> {
+        match parent {
+            Some(parent) if payload.data < parent.data => {
+                Err("timestamp went backwards".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    #[test]
+    fn test_chain_runs_validators_in_order() {
+        let chain = ValidatorChain::new().with(monotonic).with(
+            |payload: &TestPayload, _: Option<&TestPayload>| {
+                if payload.data[0] > 100 {
+                    return Err("timestamp too far ahead".to_string());
+                }
+                Ok(())
+            },
+        );
+        assert_eq!(chain.len(), 2);
+
+        let parent = stamped(10, 0);
+        assert_eq!(chain.validate(&parent, None), Ok(()));
+        assert_eq!(chain.validate(&stamped(20, 1), Some(&parent)), Ok(()));
+
+        // The first rejection wins
+        let result = chain.validate(&stamped(5, 1), Some(&parent));
+        assert_eq!(result, Err("timestamp went backwards".to_string()));
+        let result = chain.validate(&stamped(200, 1), Some(&parent));
+        assert_eq!(result, Err("timestamp too far ahead".to_string()));
+    }
+
+    #[test]
+    fn test_empty_chain_accepts() {
+        let chain = ValidatorChain::<TestPayload>::default();
+        assert!(chain.is_empty());
+        assert_eq!(chain.validate(&stamped(0, 0), None), Ok(()));
+    }
+}